@@ -0,0 +1,41 @@
+use fvm_ipld_encoding::tuple::*;
+use fvm_ipld_encoding::{Cbor, RawBytes};
+use fvm_shared::address::Address;
+use fvm_shared::bigint::bigint_ser;
+use fvm_shared::econ::TokenAmount;
+use serde::{Deserialize, Serialize};
+
+use super::json::address;
+use super::json::rawbytes;
+use super::json::tokenamount;
+
+/// Datacap actor methods (FRC-42 method numbers)
+///
+/// The datacap actor only exists from the v9 builtin-actors bundle, so its types are defined here.
+pub mod method {
+    use fvm_shared::MethodNum;
+
+    pub const TRANSFER: MethodNum = 80_475_954;
+}
+
+/// FRC-46 token transfer params
+#[derive(Clone, Debug, PartialEq, Serialize_tuple, Deserialize_tuple)]
+pub struct TransferParams {
+    pub to: Address,
+    #[serde(with = "bigint_ser")]
+    pub amount: TokenAmount,
+    pub operator_data: RawBytes,
+}
+
+impl Cbor for TransferParams {}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "TransferParams", rename_all = "PascalCase")]
+pub struct TransferParamsAPI {
+    #[serde(with = "address")]
+    pub to: Address,
+    #[serde(with = "tokenamount")]
+    pub amount: TokenAmount,
+    #[serde(with = "rawbytes")]
+    pub operator_data: RawBytes,
+}
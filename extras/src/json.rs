@@ -42,6 +42,37 @@ pub mod cid {
     }
 }
 
+/// CID as an IPLD JSON link (`{"/": "<cid>"}`), as used by Lotus
+pub mod cid_link {
+    use cid::Cid;
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use std::str::FromStr;
+
+    #[derive(Serialize, Deserialize)]
+    struct Link {
+        #[serde(rename = "/")]
+        cid: String,
+    }
+
+    pub fn serialize<S>(cid: &Cid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Link {
+            cid: cid.to_string(),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Cid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let link = Link::deserialize(deserializer)?;
+        Cid::from_str(&link.cid).map_err(de::Error::custom)
+    }
+}
+
 pub mod rawbytes {
     use base64::{decode, encode};
     use fvm_ipld_encoding::RawBytes;
@@ -155,6 +186,40 @@ pub mod tokenamount {
     }
 }
 
+pub mod option_tokenamount {
+    use super::tokenamount;
+    use fvm_shared::econ::TokenAmount;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(
+        token_amount: &Option<TokenAmount>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        #[serde(transparent)]
+        struct W<'a>(#[serde(with = "tokenamount")] &'a TokenAmount);
+
+        match token_amount {
+            Some(t) => serializer.serialize_some(&W(t)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<TokenAmount>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(transparent)]
+        struct W(#[serde(with = "tokenamount")] TokenAmount);
+
+        Ok(Option::deserialize(deserializer)?.map(|W(inner)| inner))
+    }
+}
+
 pub mod bigint {
     use fvm_shared::bigint::BigInt;
     use serde::{de, Deserialize, Deserializer, Serializer};
@@ -1,8 +1,10 @@
 pub mod cron;
+pub mod datacap;
 pub mod init;
 pub mod miner;
 pub mod multisig;
 pub mod paych;
+pub mod verifreg;
 
 pub mod message;
 pub mod signature;
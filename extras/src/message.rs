@@ -13,7 +13,8 @@ use super::json::tokenamount;
 #[derive(Serialize, Deserialize)]
 #[serde(remote = "Message", rename_all = "PascalCase")]
 pub struct MessageAPI {
-    #[serde(skip)]
+    // Left out when 0, like the messages serialized before the version was exposed
+    #[serde(default, skip_serializing_if = "is_initial_version")]
     pub version: i64,
     #[serde(with = "address")]
     pub from: Address,
@@ -33,3 +34,7 @@ pub struct MessageAPI {
     #[serde(with = "tokenamount")]
     pub gas_premium: TokenAmount,
 }
+
+fn is_initial_version(version: &i64) -> bool {
+    *version == 0
+}
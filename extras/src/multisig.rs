@@ -1,11 +1,13 @@
 use fil_actor_multisig::{
     AddSignerParams, ChangeNumApprovalsThresholdParams, ConstructorParams, LockBalanceParams,
-    ProposeParams, RemoveSignerParams, SwapSignerParams, Transaction, TxnID, TxnIDParams,
+    ProposeParams, ProposeReturn, RemoveSignerParams, SwapSignerParams, Transaction, TxnID,
+    TxnIDParams,
 };
 use fvm_ipld_encoding::RawBytes;
 use fvm_shared::address::Address;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
 use fvm_shared::MethodNum;
 use serde::{Deserialize, Serialize};
 
@@ -60,6 +62,21 @@ pub struct ProposeParamsAPI {
     pub params: RawBytes,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "ProposeReturn", rename_all = "PascalCase")]
+pub struct ProposeReturnAPI {
+    /// Id of the pending transaction, to reference in Approve and Cancel
+    #[serde(rename = "TxnID")]
+    pub txn_id: TxnID,
+    /// Whether the transaction was executed (enough approvals with the proposer's one)
+    pub applied: bool,
+    /// Exit code of the transaction execution, if applied
+    pub code: ExitCode,
+    /// Return value of the transaction execution, if applied
+    #[serde(with = "rawbytes")]
+    pub ret: RawBytes,
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(remote = "TxnIDParams", rename_all = "PascalCase")]
 pub struct TxnIDParamsAPI {
@@ -0,0 +1,16 @@
+use fil_actor_verifreg::VerifierParams;
+use fvm_shared::address::Address;
+use fvm_shared::bigint::BigInt;
+use serde::{Deserialize, Serialize};
+
+use super::json::address;
+use super::json::bigint;
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "VerifierParams", rename_all = "PascalCase")]
+pub struct VerifierParamsAPI {
+    #[serde(with = "address")]
+    pub address: Address,
+    #[serde(with = "bigint")]
+    pub allowance: BigInt,
+}
@@ -415,7 +415,7 @@ pub fn deserialize_params(
 ) -> Result<JsValue, JsValue> {
     set_panic_hook();
 
-    let params = filecoin_signer::deserialize_params(params_base64, actor_type, method as u64)
+    let params = filecoin_signer::deserialize_params_str(params_base64, actor_type, method as u64)
         .map_err(|e| JsValue::from(format!("Error deserializing parameters: {}", e)))?;
 
     let params_value = JsValue::from_serde(&params)
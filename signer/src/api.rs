@@ -1,17 +1,56 @@
 use std::convert::TryFrom;
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
 use fvm_ipld_encoding::RawBytes;
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
 use fvm_shared::message::Message;
+use fvm_shared::MethodNum;
+use num_traits::Signed;
 
 use extras::init::ExecParamsAPI;
 use extras::{
-    message::MessageAPI, miner, multisig, paych, signed_message::ref_fvm::SignedMessage,
-    signed_message::SignedMessageAPI,
+    datacap, message::MessageAPI, miner, multisig, paych, signed_message::ref_fvm::SignedMessage,
+    signed_message::SignedMessageAPI, verifreg,
 };
 
 use crate::error::SignerError;
+use crate::multisig_deprecated::ConstructorParamsV1;
+use crate::{builtin_actor_code_cid, compute_proposal_hash, ProposalHashDataAPI};
+
+/// Version of the messages built by this module, the only message version in use so far.
+/// Parsed messages keep their own version.
+pub const MESSAGE_VERSION: i64 = 0;
+
+/// Actors whose method params can be deserialized
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActorType {
+    Init,
+    Multisig,
+    PaymentChannel,
+    Verifreg,
+    Datacap,
+    Evm,
+}
+
+impl FromStr for ActorType {
+    type Err = SignerError;
+
+    fn from_str(actor_type: &str) -> Result<Self, Self::Err> {
+        match actor_type {
+            "init" => Ok(ActorType::Init),
+            "multisig" => Ok(ActorType::Multisig),
+            "paymentchannel" => Ok(ActorType::PaymentChannel),
+            "verifreg" => Ok(ActorType::Verifreg),
+            "datacap" => Ok(ActorType::Datacap),
+            "evm" => Ok(ActorType::Evm),
+            _ => Err(SignerError::UnsupportedActorType(actor_type.to_string())),
+        }
+    }
+}
 
 #[derive(Deserialize, Serialize)]
 #[serde(untagged)]
@@ -68,6 +107,12 @@ pub enum MessageParams {
     //DisputeWindowedPoStParams(fil_actor_miner::DisputeWindowedPoStParams),
     //ProveCommitAggregateParams(fil_actor_miner::ProveCommitAggregateParams),
     //ProveReplicaUpdatesParams(fil_actor_miner::ProveReplicaUpdatesParams),
+    #[serde(with = "verifreg::VerifierParamsAPI")]
+    AddVerifiedClientParams(fil_actor_verifreg::AddVerifierClientParams),
+    #[serde(with = "datacap::TransferParamsAPI")]
+    DatacapTransferParams(datacap::TransferParams),
+    /// ABI calldata of an EVM contract invocation
+    InvokeEVM(Vec<u8>),
 }
 
 impl MessageParams {
@@ -114,12 +159,28 @@ impl MessageParams {
                 .map_err(|err| SignerError::GenericString(err.to_string()))?,
             MessageParams::ApplyRewardParams(params) => RawBytes::serialize(&params)
                 .map_err(|err| SignerError::GenericString(err.to_string()))?,
+            MessageParams::AddVerifiedClientParams(params) => RawBytes::serialize(&params)
+                .map_err(|err| SignerError::GenericString(err.to_string()))?,
+            MessageParams::DatacapTransferParams(params) => RawBytes::serialize(&params)
+                .map_err(|err| SignerError::GenericString(err.to_string()))?,
+            MessageParams::InvokeEVM(calldata) => {
+                RawBytes::serialize(serde_bytes::Bytes::new(&calldata))
+                    .map_err(|err| SignerError::GenericString(err.to_string()))?
+            }
         };
 
         Ok(params_serialized)
     }
 }
 
+/// Return values of actor methods, as found in message receipts
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum MessageReturn {
+    #[serde(with = "multisig::ProposeReturnAPI")]
+    ProposeReturn(fil_actor_multisig::ProposeReturn),
+}
+
 /// Structure containing an `UnsignedMessageAPI` or a `SignedMessageAPI`
 #[derive(Deserialize, Serialize)]
 #[serde(untagged)]
@@ -146,6 +207,61 @@ impl From<MessageTx> for MessageTxAPI {
     }
 }
 
+/// Message structure where the gas fields may be left out (e.g waiting for gas estimation)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PartialMessage {
+    #[serde(default)]
+    pub version: i64,
+    #[serde(with = "extras::json::address")]
+    pub from: Address,
+    #[serde(with = "extras::json::address")]
+    pub to: Address,
+    pub nonce: u64,
+    #[serde(with = "extras::json::tokenamount")]
+    pub value: TokenAmount,
+    pub method: MethodNum,
+    #[serde(with = "extras::json::rawbytes")]
+    pub params: RawBytes,
+    #[serde(default)]
+    pub gas_limit: Option<i64>,
+    #[serde(default, with = "extras::json::option_tokenamount")]
+    pub gas_fee_cap: Option<TokenAmount>,
+    #[serde(default, with = "extras::json::option_tokenamount")]
+    pub gas_premium: Option<TokenAmount>,
+}
+
+impl PartialMessage {
+    /// Build the complete message using the given gas values for the fields that are missing.
+    pub fn fill_gas(
+        self,
+        gas_limit: i64,
+        gas_fee_cap: TokenAmount,
+        gas_premium: TokenAmount,
+    ) -> Message {
+        Message {
+            version: self.version,
+            from: self.from,
+            to: self.to,
+            sequence: self.nonce,
+            value: self.value,
+            method_num: self.method,
+            params: self.params,
+            gas_limit: self.gas_limit.unwrap_or(gas_limit),
+            gas_fee_cap: self.gas_fee_cap.unwrap_or(gas_fee_cap),
+            gas_premium: self.gas_premium.unwrap_or(gas_premium),
+        }
+    }
+}
+
+/// Gas values of a message
+#[derive(Debug, Clone)]
+pub struct GasParams {
+    pub gas_limit: i64,
+    pub gas_fee_cap: TokenAmount,
+    pub gas_premium: TokenAmount,
+}
+
 /// Create multisig message api structure
 #[cfg_attr(feature = "with-arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
@@ -214,9 +330,66 @@ pub struct ProposalMessageParamsAPI {
 
 impl MessageTxAPI {
     pub fn get_message(&self) -> Message {
+        self.message().to_owned()
+    }
+
+    /// Sender of the message
+    pub fn from(&self) -> &Address {
+        &self.message().from
+    }
+
+    /// Recipient of the message
+    pub fn to(&self) -> &Address {
+        &self.message().to
+    }
+
+    /// Amount sent with the message
+    pub fn value(&self) -> &TokenAmount {
+        &self.message().value
+    }
+
+    /// Nonce (sequence) of the message
+    pub fn nonce(&self) -> u64 {
+        self.message().sequence
+    }
+
+    /// `true` if the message carries a signature
+    pub fn is_signed(&self) -> bool {
+        matches!(self, MessageTxAPI::SignedMessage(_))
+    }
+
+    /// Check that the gas values are non-negative and that the fee cap covers the premium
+    pub fn check_gas_params(&self) -> Result<(), SignerError> {
+        let message = self.message();
+
+        if message.gas_limit < 0 {
+            return Err(SignerError::InvalidGasParams(
+                "`gas_limit` can't be negative".to_string(),
+            ));
+        }
+        if message.gas_fee_cap.is_negative() {
+            return Err(SignerError::InvalidGasParams(
+                "`gas_fee_cap` can't be negative".to_string(),
+            ));
+        }
+        if message.gas_premium.is_negative() {
+            return Err(SignerError::InvalidGasParams(
+                "`gas_premium` can't be negative".to_string(),
+            ));
+        }
+        if message.gas_fee_cap < message.gas_premium {
+            return Err(SignerError::InvalidGasParams(
+                "`gas_premium` can't exceed `gas_fee_cap`".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn message(&self) -> &Message {
         match self {
-            MessageTxAPI::Message(message) => message.to_owned(),
-            MessageTxAPI::SignedMessage(signed_message) => signed_message.message.to_owned(),
+            MessageTxAPI::Message(message) => message,
+            MessageTxAPI::SignedMessage(signed_message) => &signed_message.message,
         }
     }
 }
@@ -227,17 +400,17 @@ pub struct MessageTxNetwork {
     pub testnet: bool,
 }
 
-impl TryFrom<MessageTxNetwork> for MessageTxAPI {
-    type Error = SignerError;
-
-    fn try_from(message_tx_network: MessageTxNetwork) -> Result<MessageTxAPI, Self::Error> {
-        let network = if message_tx_network.testnet {
+impl MessageTxNetwork {
+    /// Set the network on the message addresses without checking the gas values. Prefer
+    /// `MessageTxAPI::try_from`, which rejects inconsistent gas values.
+    pub fn into_unchecked(self) -> MessageTxAPI {
+        let network = if self.testnet {
             fvm_shared::address::Network::Testnet
         } else {
             fvm_shared::address::Network::Mainnet
         };
 
-        match message_tx_network.message_tx {
+        match self.message_tx {
             MessageTxAPI::Message(message_tx) => {
                 let mut to_address: fvm_shared::address::Address = message_tx.to.to_owned();
                 to_address.set_network(network);
@@ -251,7 +424,7 @@ impl TryFrom<MessageTxNetwork> for MessageTxAPI {
                     ..message_tx
                 };
 
-                Ok(MessageTxAPI::Message(message_with_network))
+                MessageTxAPI::Message(message_with_network)
             }
             MessageTxAPI::SignedMessage(message_tx) => {
                 let mut to_address: fvm_shared::address::Address = message_tx.message.to.to_owned();
@@ -274,18 +447,503 @@ impl TryFrom<MessageTxNetwork> for MessageTxAPI {
                     signature: message_tx.signature,
                 };
 
-                Ok(MessageTxAPI::SignedMessage(signed_message))
+                MessageTxAPI::SignedMessage(signed_message)
             }
         }
     }
 }
 
+impl TryFrom<MessageTxNetwork> for MessageTxAPI {
+    type Error = SignerError;
+
+    fn try_from(message_tx_network: MessageTxNetwork) -> Result<MessageTxAPI, Self::Error> {
+        message_tx_network.message_tx.check_gas_params()?;
+
+        Ok(message_tx_network.into_unchecked())
+    }
+}
+
+fn parse_token_amount(amount: &str, field: &str) -> Result<TokenAmount, SignerError> {
+    let amount = TokenAmount::from_str(amount)?;
+
+    if amount.is_negative() {
+        return Err(SignerError::NegativeAmount(field.to_string()));
+    }
+
+    Ok(amount)
+}
+
+/// Build a message from its string and primitive field values, validating each of them.
+///
+/// # Arguments
+///
+/// * `from` - The sender address
+/// * `to` - The recipient address
+/// * `value` - The amount in attoFIL
+/// * `method` - The method number
+/// * `params_b64` - The serialized params as a base64 string (empty for none)
+/// * `nonce` - The nonce of the `from` account
+/// * `gas_limit` - The gas limit
+/// * `gas_fee_cap` - The gas fee cap in attoFIL
+/// * `gas_premium` - The gas premium in attoFIL
+///
+#[allow(clippy::too_many_arguments)]
+pub fn new_message(
+    from: &str,
+    to: &str,
+    value: &str,
+    method: u64,
+    params_b64: &str,
+    nonce: u64,
+    gas_limit: i64,
+    gas_fee_cap: &str,
+    gas_premium: &str,
+) -> Result<Message, SignerError> {
+    if gas_limit < 0 {
+        return Err(SignerError::InvalidGasParams(
+            "`gas_limit` can't be negative".to_string(),
+        ));
+    }
+
+    Ok(Message {
+        version: MESSAGE_VERSION,
+        from: Address::from_str(from)?,
+        to: Address::from_str(to)?,
+        sequence: nonce,
+        value: parse_token_amount(value, "value")?,
+        method_num: method,
+        params: RawBytes::new(base64::decode(params_b64)?),
+        gas_limit,
+        gas_fee_cap: parse_token_amount(gas_fee_cap, "gas_fee_cap")?,
+        gas_premium: parse_token_amount(gas_premium, "gas_premium")?,
+    })
+}
+
+/// Return an error if the message sends a positive value from an address to itself with
+/// the send method, which is usually a copy-paste mistake.
+///
+/// # Arguments
+///
+/// * `message` - The message to check
+///
+pub fn check_self_transfer(message: &Message) -> Result<(), SignerError> {
+    if message.from.payload() == message.to.payload()
+        && message.method_num == fvm_shared::METHOD_SEND
+        && message.value.is_positive()
+    {
+        return Err(SignerError::GenericString(
+            "Message is a transfer from an address to itself".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Same as `new_message` but also rejects self-transfers (see `check_self_transfer`).
+#[allow(clippy::too_many_arguments)]
+pub fn new_message_checked(
+    from: &str,
+    to: &str,
+    value: &str,
+    method: u64,
+    params_b64: &str,
+    nonce: u64,
+    gas_limit: i64,
+    gas_fee_cap: &str,
+    gas_premium: &str,
+) -> Result<Message, SignerError> {
+    let message = new_message(
+        from,
+        to,
+        value,
+        method,
+        params_b64,
+        nonce,
+        gas_limit,
+        gas_fee_cap,
+        gas_premium,
+    )?;
+
+    check_self_transfer(&message)?;
+
+    Ok(message)
+}
+
+/// Build the multisig Propose message swapping `old_signer` for `new_signer` and the proposal
+/// hash that later approvals will have to reference. Gas fields are left to zero for estimation.
+///
+/// The proposal hash includes the requester, so `from` must be the ID address of the proposer
+/// for the hash to match the one computed on chain.
+///
+/// # Arguments
+///
+/// * `multisig_address` - The multisig actor address
+/// * `from` - The signer proposing the swap
+/// * `old_signer` - The signer to remove
+/// * `new_signer` - The signer to add
+/// * `nonce` - The nonce of the `from` account
+///
+pub fn build_swap_signer_proposal(
+    multisig_address: Address,
+    from: Address,
+    old_signer: Address,
+    new_signer: Address,
+    nonce: u64,
+) -> Result<(Message, String), SignerError> {
+    let swap_params = RawBytes::serialize(fil_actor_multisig::SwapSignerParams {
+        from: old_signer,
+        to: new_signer,
+    })
+    .map_err(|err| SignerError::GenericString(err.to_string()))?;
+
+    let proposal_hash = compute_proposal_hash(ProposalHashDataAPI {
+        requester: Some(from),
+        to: multisig_address,
+        value: TokenAmount::from(0),
+        method: fil_actor_multisig::Method::SwapSigner as MethodNum,
+        params: swap_params.clone(),
+    })?;
+
+    // Gas fields are left to zero for estimation
+    let message = multisig_propose(
+        multisig_address,
+        multisig_address,
+        TokenAmount::from(0),
+        fil_actor_multisig::Method::SwapSigner as MethodNum,
+        swap_params,
+        from,
+        nonce,
+        GasParams {
+            gas_limit: 0,
+            gas_fee_cap: TokenAmount::from(0),
+            gas_premium: TokenAmount::from(0),
+        },
+    )?;
+
+    Ok((message, proposal_hash))
+}
+
+/// Build the init actor Exec message creating a multisig. The multisig is funded with `value`.
+///
+/// # Arguments
+///
+/// * `signers` - The multisig signers
+/// * `threshold` - The number of approvals required
+/// * `unlock_duration` - The vesting duration of the initial balance (0 if none)
+/// * `start_epoch` - The epoch the vesting starts at
+/// * `value` - The initial balance of the multisig
+/// * `from` - The address creating the multisig
+/// * `nonce` - The nonce of the `from` account
+/// * `gas` - The gas values of the message
+/// * `actors_version` - The actors version giving the multisig code CID (1 to 7)
+///
+#[allow(clippy::too_many_arguments)]
+pub fn create_multisig_message(
+    signers: Vec<Address>,
+    threshold: u64,
+    unlock_duration: ChainEpoch,
+    start_epoch: ChainEpoch,
+    value: TokenAmount,
+    from: Address,
+    nonce: u64,
+    gas: GasParams,
+    actors_version: u32,
+) -> Result<Message, SignerError> {
+    let constructor_params = if actors_version == 1 {
+        // `start_epoch` was added in v2
+        if start_epoch != 0 {
+            return Err(SignerError::GenericString(
+                "Multisig v1 doesn't support a start epoch".to_string(),
+            ));
+        }
+
+        RawBytes::serialize(ConstructorParamsV1 {
+            signers,
+            num_approvals_threshold: threshold,
+            unlock_duration,
+        })
+    } else {
+        RawBytes::serialize(fil_actor_multisig::ConstructorParams {
+            signers,
+            num_approvals_threshold: threshold,
+            unlock_duration,
+            start_epoch,
+        })
+    }
+    .map_err(|err| SignerError::GenericString(err.to_string()))?;
+
+    build_exec_message(
+        builtin_actor_code_cid(actors_version, "multisig")?,
+        constructor_params,
+        value,
+        from,
+        nonce,
+        gas,
+    )
+}
+
+/// Build the init actor Exec message creating a payment channel from `from` to `to`, funded
+/// with `value`. The channel address can be computed beforehand with `compute_paych_address`.
+///
+/// # Arguments
+///
+/// * `from` - The payer, creating the channel
+/// * `to` - The payee
+/// * `value` - The initial balance of the channel
+/// * `nonce` - The nonce of the `from` account
+/// * `gas` - The gas values of the message
+/// * `actors_version` - The actors version giving the payment channel code CID (1 to 7)
+///
+pub fn create_paych_message(
+    from: Address,
+    to: Address,
+    value: TokenAmount,
+    nonce: u64,
+    gas: GasParams,
+    actors_version: u32,
+) -> Result<Message, SignerError> {
+    let constructor_params = RawBytes::serialize(fil_actor_paych::ConstructorParams { from, to })
+        .map_err(|err| SignerError::GenericString(err.to_string()))?;
+
+    build_exec_message(
+        builtin_actor_code_cid(actors_version, "paymentchannel")?,
+        constructor_params,
+        value,
+        from,
+        nonce,
+        gas,
+    )
+}
+
+fn build_exec_message(
+    code_cid: cid::Cid,
+    constructor_params: RawBytes,
+    value: TokenAmount,
+    from: Address,
+    nonce: u64,
+    gas: GasParams,
+) -> Result<Message, SignerError> {
+    let params = RawBytes::serialize(fil_actor_init::ExecParams {
+        code_cid,
+        constructor_params,
+    })
+    .map_err(|err| SignerError::GenericString(err.to_string()))?;
+
+    // Init actor, on the same network as the sender
+    let mut init_actor = Address::new_id(1);
+    init_actor.set_network(from.network());
+
+    Ok(Message {
+        version: MESSAGE_VERSION,
+        from,
+        to: init_actor,
+        sequence: nonce,
+        value,
+        method_num: fil_actor_init::Method::Exec as MethodNum,
+        params,
+        gas_limit: gas.gas_limit,
+        gas_fee_cap: gas.gas_fee_cap,
+        gas_premium: gas.gas_premium,
+    })
+}
+
+/// Build the multisig Propose message wrapping an inner call (`to`, `value`, `method`, `params`)
+/// that the multisig will execute once enough signers approved it.
+///
+/// # Arguments
+///
+/// * `multisig_address` - The multisig actor address
+/// * `to` - The recipient of the inner call
+/// * `value` - The amount sent by the multisig with the inner call
+/// * `method` - The method number of the inner call
+/// * `params` - The serialized params of the inner call
+/// * `from` - The signer proposing the transaction
+/// * `nonce` - The nonce of the `from` account
+/// * `gas` - The gas values of the message
+///
+#[allow(clippy::too_many_arguments)]
+pub fn multisig_propose(
+    multisig_address: Address,
+    to: Address,
+    value: TokenAmount,
+    method: MethodNum,
+    params: RawBytes,
+    from: Address,
+    nonce: u64,
+    gas: GasParams,
+) -> Result<Message, SignerError> {
+    let propose_params = RawBytes::serialize(fil_actor_multisig::ProposeParams {
+        to,
+        value,
+        method,
+        params,
+    })
+    .map_err(|err| SignerError::GenericString(err.to_string()))?;
+
+    Ok(Message {
+        version: MESSAGE_VERSION,
+        from,
+        to: multisig_address,
+        sequence: nonce,
+        value: TokenAmount::from(0),
+        method_num: fil_actor_multisig::Method::Propose as MethodNum,
+        params: propose_params,
+        gas_limit: gas.gas_limit,
+        gas_fee_cap: gas.gas_fee_cap,
+        gas_premium: gas.gas_premium,
+    })
+}
+
+/// Build the multisig Approve message for a pending transaction. The proposal hash is
+/// recomputed from `proposal_hash_data` so that it always matches the proposal.
+///
+/// # Arguments
+///
+/// * `multisig_address` - The multisig actor address
+/// * `txn_id` - The id of the pending transaction
+/// * `proposal_hash_data` - The proposal being approved
+/// * `from` - The signer approving the transaction
+/// * `nonce` - The nonce of the `from` account
+/// * `gas` - The gas values of the message
+///
+pub fn multisig_approve(
+    multisig_address: Address,
+    txn_id: i64,
+    proposal_hash_data: ProposalHashDataAPI,
+    from: Address,
+    nonce: u64,
+    gas: GasParams,
+) -> Result<Message, SignerError> {
+    build_txn_id_message(
+        fil_actor_multisig::Method::Approve as MethodNum,
+        multisig_address,
+        txn_id,
+        proposal_hash_data,
+        from,
+        nonce,
+        gas,
+    )
+}
+
+/// Build the multisig Cancel message for a pending transaction. The proposal hash is
+/// recomputed from `proposal_hash_data` so that it always matches the proposal.
+///
+/// # Arguments
+///
+/// * `multisig_address` - The multisig actor address
+/// * `txn_id` - The id of the pending transaction
+/// * `proposal_hash_data` - The proposal being cancelled
+/// * `from` - The signer cancelling the transaction
+/// * `nonce` - The nonce of the `from` account
+/// * `gas` - The gas values of the message
+///
+pub fn multisig_cancel(
+    multisig_address: Address,
+    txn_id: i64,
+    proposal_hash_data: ProposalHashDataAPI,
+    from: Address,
+    nonce: u64,
+    gas: GasParams,
+) -> Result<Message, SignerError> {
+    build_txn_id_message(
+        fil_actor_multisig::Method::Cancel as MethodNum,
+        multisig_address,
+        txn_id,
+        proposal_hash_data,
+        from,
+        nonce,
+        gas,
+    )
+}
+
+fn build_txn_id_message(
+    method_num: MethodNum,
+    multisig_address: Address,
+    txn_id: i64,
+    proposal_hash_data: ProposalHashDataAPI,
+    from: Address,
+    nonce: u64,
+    gas: GasParams,
+) -> Result<Message, SignerError> {
+    let proposal_hash = base64::decode(compute_proposal_hash(proposal_hash_data)?)?;
+
+    let params = RawBytes::serialize(fil_actor_multisig::TxnIDParams {
+        id: fil_actor_multisig::TxnID(txn_id),
+        proposal_hash,
+    })
+    .map_err(|err| SignerError::GenericString(err.to_string()))?;
+
+    Ok(Message {
+        version: MESSAGE_VERSION,
+        from,
+        to: multisig_address,
+        sequence: nonce,
+        value: TokenAmount::from(0),
+        method_num,
+        params,
+        gas_limit: gas.gas_limit,
+        gas_fee_cap: gas.gas_fee_cap,
+        gas_premium: gas.gas_premium,
+    })
+}
+
+/// Build the payment channel UpdateChannelState message redeeming a signed voucher.
+///
+/// # Arguments
+///
+/// * `channel` - The payment channel address
+/// * `from` - The address redeeming the voucher
+/// * `voucher_base64` - The signed voucher as a base64 string
+/// * `secret` - The secret preimage required by the voucher (empty if none)
+/// * `nonce` - The nonce of the `from` account
+/// * `gas` - The gas values of the message
+///
+pub fn build_voucher_redemption_message(
+    channel: Address,
+    from: Address,
+    voucher_base64: String,
+    secret: Vec<u8>,
+    nonce: u64,
+    gas: GasParams,
+) -> Result<Message, SignerError> {
+    let voucher_bytes = base64::decode(voucher_base64)?;
+    let voucher: fil_actor_paych::SignedVoucher = fvm_ipld_encoding::from_slice(&voucher_bytes)?;
+
+    if voucher.channel_addr.payload() != channel.payload() {
+        return Err(SignerError::AddressMismatch);
+    }
+
+    let params = RawBytes::serialize(fil_actor_paych::UpdateChannelStateParams {
+        sv: voucher,
+        secret,
+    })
+    .map_err(|err| SignerError::GenericString(err.to_string()))?;
+
+    Ok(Message {
+        version: MESSAGE_VERSION,
+        from,
+        to: channel,
+        sequence: nonce,
+        value: TokenAmount::from(0),
+        method_num: fil_actor_paych::Method::UpdateChannelState as MethodNum,
+        params,
+        gas_limit: gas.gas_limit,
+        gas_fee_cap: gas.gas_fee_cap,
+        gas_premium: gas.gas_premium,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use fvm_ipld_encoding::{from_slice, to_vec};
+    use fvm_shared::econ::TokenAmount;
     use hex::{decode, encode};
+    use std::str::FromStr;
 
-    use crate::api::MessageTxAPI;
+    use crate::api::{new_message, new_message_checked, MessageTxAPI, PartialMessage};
+    use crate::error::SignerError;
+    use extras::signed_message::ref_fvm::SignedMessage;
+    use fvm_shared::crypto::signature::Signature;
 
     const EXAMPLE_UNSIGNED_MESSAGE: &str = r#"
         {
@@ -346,6 +1004,115 @@ mod tests {
         assert_eq!(EXPECTED_MESSAGE_JSON, message_json)
     }
 
+    #[test]
+    fn message_tx_accessors() {
+        let cbor_buffer = decode(EXAMPLE_CBOR_DATA).unwrap();
+        let message: fvm_shared::message::Message = from_slice(&cbor_buffer).unwrap();
+
+        let unsigned = MessageTxAPI::Message(message.clone());
+        let signed = MessageTxAPI::SignedMessage(SignedMessage {
+            message: message.clone(),
+            signature: Signature::new_secp256k1(vec![0; 65]),
+        });
+
+        for message_tx in [&unsigned, &signed] {
+            assert_eq!(message_tx.from(), &message.from);
+            assert_eq!(message_tx.to(), &message.to);
+            assert_eq!(message_tx.value(), &TokenAmount::from(100000));
+            assert_eq!(message_tx.nonce(), 1);
+        }
+
+        assert!(!unsigned.is_signed());
+        assert!(signed.is_signed());
+    }
+
+    #[test]
+    fn partial_message_fill_gas() {
+        const EXAMPLE_PARTIAL_MESSAGE: &str = r#"
+        {
+            "To": "f17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy",
+            "From": "f1xcbgdhkgkwht3hrrnui3jdopeejsoas2rujnkdi",
+            "Nonce": 1,
+            "Value": "100000",
+            "Method": 0,
+            "Params": ""
+        }"#;
+
+        let partial_message: PartialMessage =
+            serde_json::from_str(EXAMPLE_PARTIAL_MESSAGE).expect("FIXME");
+
+        assert!(partial_message.gas_limit.is_none());
+        assert!(partial_message.gas_fee_cap.is_none());
+        assert!(partial_message.gas_premium.is_none());
+
+        let message = partial_message.fill_gas(
+            25000,
+            TokenAmount::from_str("1").unwrap(),
+            TokenAmount::from_str("1").unwrap(),
+        );
+
+        let message_cbor: Vec<u8> = to_vec(&message).expect("Cbor serialization failed");
+
+        assert_eq!(EXAMPLE_CBOR_DATA, encode(message_cbor))
+    }
+
+    #[test]
+    fn new_message_from_fields() {
+        let message = new_message(
+            "f1xcbgdhkgkwht3hrrnui3jdopeejsoas2rujnkdi",
+            "f17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy",
+            "100000",
+            0,
+            "",
+            1,
+            25000,
+            "1",
+            "1",
+        )
+        .unwrap();
+
+        let message_cbor: Vec<u8> = to_vec(&message).expect("Cbor serialization failed");
+        assert_eq!(EXAMPLE_CBOR_DATA, encode(message_cbor));
+
+        assert!(new_message("f1invalid", "f01", "1", 0, "", 1, 1, "1", "1").is_err());
+        assert!(matches!(
+            new_message("f01", "f02", "-1", 0, "", 1, 1, "1", "1"),
+            Err(SignerError::NegativeAmount(field)) if field == "value"
+        ));
+        assert!(new_message("f01", "f02", "1", 0, "not base64!", 1, 1, "1", "1").is_err());
+        assert!(matches!(
+            new_message("f01", "f02", "1", 0, "", 1, -1, "1", "1"),
+            Err(SignerError::InvalidGasParams(_))
+        ));
+    }
+
+    #[test]
+    fn reject_self_transfer() {
+        const ADDRESS: &str = "f1xcbgdhkgkwht3hrrnui3jdopeejsoas2rujnkdi";
+        const TESTNET_ADDRESS: &str = "t1xcbgdhkgkwht3hrrnui3jdopeejsoas2rujnkdi";
+
+        // Self transfers are only rejected when asked for
+        assert!(new_message(ADDRESS, ADDRESS, "1", 0, "", 1, 1, "1", "1").is_ok());
+        assert!(new_message_checked(ADDRESS, ADDRESS, "1", 0, "", 1, 1, "1", "1").is_err());
+        assert!(new_message_checked(ADDRESS, TESTNET_ADDRESS, "1", 0, "", 1, 1, "1", "1").is_err());
+
+        // Zero value or a method call to itself are legitimate
+        assert!(new_message_checked(ADDRESS, ADDRESS, "0", 0, "", 1, 1, "1", "1").is_ok());
+        assert!(new_message_checked(ADDRESS, ADDRESS, "1", 2, "", 1, 1, "1", "1").is_ok());
+        assert!(new_message_checked(
+            ADDRESS,
+            "f17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy",
+            "1",
+            0,
+            "",
+            1,
+            1,
+            "1",
+            "1"
+        )
+        .is_ok());
+    }
+
     #[test]
     fn conversion_signed_messages() {
         const EXAMPLE_SIGNED_MESSAGE: &str = r#"{
@@ -1,4 +1,5 @@
 use core::{array::TryFromSliceError, num::ParseIntError};
+use fvm_shared::crypto::signature::SignatureType;
 use hmac::crypto_mac::InvalidKeyLength;
 use thiserror::Error;
 
@@ -41,6 +42,81 @@ pub enum SignerError {
     // CID error
     #[error("Cannot read CID from string | {0}")]
     CidError(#[from] cid::Error),
+    /// Signature length doesn't match its type
+    #[error("Invalid signature length for {0:?}: expected {1} bytes, got {2}")]
+    InvalidSignatureLength(SignatureType, usize, usize),
+    /// Address protocol can't be used to sign
+    #[error("Unknown signing protocol")]
+    UnknownSigningProtocol,
+    /// Actor type (or code CID) not handled
+    #[error("Actor type not supported: `{0}`")]
+    UnsupportedActorType(String),
+    /// Voucher has no signature
+    #[error("Voucher not signed")]
+    VoucherNotSigned,
+    /// Address doesn't match the expected one
+    #[error("Address doesn't match the expected address")]
+    AddressMismatch,
+    /// Key doesn't have the expected length
+    #[error("Invalid key length: expected {expected} bytes, got {got}")]
+    InvalidKeyLength { expected: usize, got: usize },
+    /// CBOR data ends before the end of the item
+    #[error("Incomplete CBOR data")]
+    IncompleteCbor,
+    /// Unknown mnemonic language code
+    #[error("Unknown language code: `{0}`")]
+    UnknownLanguageCode(String),
+    /// Mnemonic doesn't have 12, 15, 18, 21 or 24 words
+    #[error("Invalid mnemonic word count: {0}")]
+    InvalidMnemonicWordCount(usize),
+    /// Mnemonic contains a word that is not in the wordlist
+    #[error("Invalid word in mnemonic")]
+    InvalidMnemonicWord,
+    /// Mnemonic checksum doesn't match
+    #[error("Invalid mnemonic checksum")]
+    InvalidMnemonicChecksum,
+    /// A signed message was given where an unsigned message is expected
+    #[error("Expected an unsigned message, got a signed message")]
+    UnexpectedSignedMessage,
+    /// Gas limit, fee cap or premium are not consistent
+    #[error("Invalid gas params: {0}")]
+    InvalidGasParams(String),
+    /// Actor code CID not handled
+    #[error("Code CID not supported: `{0}`")]
+    UnsupportedCodeCid(String),
+    /// Secp256k1 signature S value is not in the lower half of the curve order
+    #[error("Malleable signature: high S value")]
+    MalleableSignature,
+    /// Message exceeds the limits checked before signing
+    #[error("Message limit exceeded: {0}")]
+    MessageLimitExceeded(String),
+    /// CBOR data doesn't use the canonical encoding
+    #[error("Non canonical CBOR encoding")]
+    NonCanonicalCbor,
+    /// Private key string is not valid base64
+    #[error("Invalid base64 private key: {0}")]
+    InvalidBase64Key(String),
+    /// Chain id too large to compute an EIP-155 `v` value
+    #[error("Chain id too large for EIP-155: {0}")]
+    ChainIdTooLarge(u64),
+    /// EIP-155 `v` value doesn't match the chain id
+    #[error("Invalid EIP-155 v value {v} for chain id {chain_id}")]
+    InvalidEip155V { v: u64, chain_id: u64 },
+    /// No private key matches the address
+    #[error("No key found for address {0}")]
+    KeyNotFound(String),
+    /// Key wasn't derived from a seed and has no BIP32 chain code
+    #[error("Extended key has no chain code")]
+    MissingChainCode,
+    /// Number of keys doesn't match the number of messages
+    #[error("Expected one key per message, got {messages} messages and {keys} keys")]
+    LengthMismatch { messages: usize, keys: usize },
+    /// Token amount is negative
+    #[error("`{0}` can't be negative")]
+    NegativeAmount(String),
+    /// Delegated (f4) address can't be parsed
+    #[error("Invalid delegated address: {0}")]
+    InvalidDelegatedAddress(String),
 }
 
 #[cfg(feature = "with-ffi-support")]
@@ -59,6 +135,31 @@ impl From<SignerError> for ffi_support::ExternError {
             SignerError::DecodeError(_) => 11,
             SignerError::DeserializeError(_) => 12,
             SignerError::CidError(_) => 13,
+            SignerError::InvalidSignatureLength(..) => 14,
+            SignerError::UnknownSigningProtocol => 15,
+            SignerError::UnsupportedActorType(_) => 16,
+            SignerError::VoucherNotSigned => 17,
+            SignerError::AddressMismatch => 18,
+            SignerError::InvalidKeyLength { .. } => 19,
+            SignerError::IncompleteCbor => 20,
+            SignerError::UnknownLanguageCode(_) => 21,
+            SignerError::InvalidMnemonicWordCount(_) => 22,
+            SignerError::InvalidMnemonicWord => 23,
+            SignerError::InvalidMnemonicChecksum => 24,
+            SignerError::UnexpectedSignedMessage => 25,
+            SignerError::InvalidGasParams(_) => 26,
+            SignerError::UnsupportedCodeCid(_) => 27,
+            SignerError::MalleableSignature => 28,
+            SignerError::MessageLimitExceeded(_) => 29,
+            SignerError::NonCanonicalCbor => 30,
+            SignerError::InvalidBase64Key(_) => 31,
+            SignerError::ChainIdTooLarge(_) => 32,
+            SignerError::InvalidEip155V { .. } => 33,
+            SignerError::KeyNotFound(_) => 34,
+            SignerError::MissingChainCode => 35,
+            SignerError::LengthMismatch { .. } => 36,
+            SignerError::NegativeAmount(_) => 37,
+            SignerError::InvalidDelegatedAddress(_) => 38,
         };
         Self::new_error(ffi_support::ErrorCode::new(code), e.to_string())
     }
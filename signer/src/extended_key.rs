@@ -6,7 +6,7 @@ use hmac::{Hmac, Mac, NewMac};
 use sha2::Sha512;
 use std::convert::TryFrom;
 use std::fmt;
-use zeroize::Zeroize;
+use zeroize::{Zeroize, Zeroizing};
 use zx_bip44::BIP44Path;
 
 const HMAC_SEED: &[u8; 12] = b"Bitcoin seed";
@@ -41,13 +41,17 @@ impl TryFrom<&[u8]> for ExtendedSecretKey {
         let mut hmac = HmacSha512::new_varkey(HMAC_SEED)?;
         hmac.update(seed);
 
-        let hmac_code = hmac.finalize().into_bytes();
-        let (master_private_key, master_chain_code) = hmac_code.split_at(32);
+        let mut hmac_code = hmac.finalize().into_bytes();
+        let master_key = {
+            let (master_private_key, master_chain_code) = hmac_code.split_at(32);
 
-        ExtendedSecretKey::new(
-            SecretKey::parse_slice(master_private_key)?,
-            master_chain_code,
-        )
+            SecretKey::parse_slice(master_private_key)
+                .map_err(SignerError::from)
+                .and_then(|secret_key| ExtendedSecretKey::new(secret_key, master_chain_code))
+        };
+        hmac_code.as_mut_slice().zeroize();
+
+        master_key
     }
 }
 
@@ -68,7 +72,6 @@ impl ExtendedSecretKey {
     }
 
     #[inline]
-    #[allow(dead_code)]
     pub fn chain_code(&self) -> [u8; SECRET_KEY_SIZE] {
         self.chain_code.0
     }
@@ -95,17 +98,23 @@ impl ExtendedSecretKey {
         } else {
             // Hardened
             hmac.update(&[0u8]);
-            hmac.update(&self.secret_key());
+            hmac.update(&*Zeroizing::new(self.secret_key()));
             hmac.update(&child_index.to_be_bytes());
         }
 
-        let hmac_result = hmac.finalize().into_bytes();
-        let (secret_key_shift, child_chain_code) = hmac_result.split_at(32);
+        let mut hmac_result = hmac.finalize().into_bytes();
+        let child_key = {
+            let (secret_key_shift, child_chain_code) = hmac_result.split_at(32);
 
-        let mut child_secret_key = self.secret_key;
-        child_secret_key.tweak_add_assign(&SecretKey::parse_slice(secret_key_shift)?)?;
+            let mut child_secret_key = self.secret_key;
+            SecretKey::parse_slice(secret_key_shift)
+                .and_then(|shift| child_secret_key.tweak_add_assign(&shift))
+                .map_err(SignerError::from)
+                .and_then(|_| ExtendedSecretKey::new(child_secret_key, child_chain_code))
+        };
+        hmac_result.as_mut_slice().zeroize();
 
-        ExtendedSecretKey::new(child_secret_key, child_chain_code)
+        child_key
     }
 
     pub fn derive_bip44(&self, path: &BIP44Path) -> Result<ExtendedSecretKey, SignerError> {
@@ -117,6 +126,83 @@ impl ExtendedSecretKey {
 
         Ok(child4)
     }
+
+    pub fn neuter(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            public_key: PublicKey::from_secret_key(&self.secret_key),
+            chain_code: self.chain_code.0,
+        }
+    }
+}
+
+/// Extended public key (public key and chain code), able to derive non-hardened children
+pub struct ExtendedPublicKey {
+    public_key: PublicKey,
+    chain_code: [u8; 32],
+}
+
+impl fmt::Display for ExtendedPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "PublicKey/ChainCode:  {:?}/{:?}",
+            hex::encode(&self.public_key_compressed()),
+            hex::encode(&self.chain_code)
+        )
+    }
+}
+
+impl ExtendedPublicKey {
+    pub fn new(public_key: PublicKey, chain_code: &[u8]) -> Result<Self, SignerError> {
+        let mut tmp = [0u8; 32];
+        if chain_code.len() != tmp.len() {
+            return Err(SignerError::InvalidKeyLength {
+                expected: tmp.len(),
+                got: chain_code.len(),
+            });
+        }
+        tmp.copy_from_slice(chain_code);
+
+        Ok(ExtendedPublicKey {
+            public_key,
+            chain_code: tmp,
+        })
+    }
+
+    #[inline]
+    pub fn chain_code(&self) -> [u8; 32] {
+        self.chain_code
+    }
+
+    #[inline]
+    pub fn public_key(&self) -> [u8; FULL_PUBLIC_KEY_SIZE] {
+        self.public_key.serialize()
+    }
+
+    #[inline]
+    pub fn public_key_compressed(&self) -> [u8; COMPRESSED_PUBLIC_KEY_SIZE] {
+        self.public_key.serialize_compressed()
+    }
+
+    pub fn derive_child(&self, child_index: u32) -> Result<ExtendedPublicKey, SignerError> {
+        if child_index & HARDENED_BIT != 0 {
+            return Err(SignerError::GenericString(
+                "Cannot derive a hardened child from a public key".to_string(),
+            ));
+        }
+
+        let mut hmac = Hmac::<Sha512>::new_varkey(&self.chain_code)?;
+        hmac.update(&self.public_key_compressed());
+        hmac.update(&child_index.to_be_bytes());
+
+        let hmac_result = hmac.finalize().into_bytes();
+        let (public_key_shift, child_chain_code) = hmac_result.split_at(32);
+
+        let mut child_public_key = self.public_key;
+        child_public_key.tweak_add_assign(&SecretKey::parse_slice(public_key_shift)?)?;
+
+        ExtendedPublicKey::new(child_public_key, child_chain_code)
+    }
 }
 
 #[cfg(test)]
@@ -206,6 +292,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn derive_public_child() {
+        let phrase = "pumpkin sell climb ten list proof embark finish zero voyage congress outdoor domain city cannon leave select visual know waste tonight sauce load lift";
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+        let seed = Seed::new(&mnemonic, "");
+        let master = ExtendedSecretKey::try_from(seed.as_bytes()).unwrap();
+
+        let account = master
+            .derive_child_key(44 | HARDENED_BIT)
+            .unwrap()
+            .derive_child_key(461 | HARDENED_BIT)
+            .unwrap()
+            .derive_child_key(0)
+            .unwrap()
+            .derive_child_key(0)
+            .unwrap();
+
+        let xpub = account.neuter();
+
+        for index in 0..3 {
+            let child_esk = account.derive_child_key(index).unwrap();
+            let child_xpub = xpub.derive_child(index).unwrap();
+
+            assert_eq!(child_xpub.public_key(), child_esk.public_key());
+            assert_eq!(child_xpub.chain_code(), child_esk.chain_code());
+        }
+
+        // m/44'/461'/0/0/0
+        let path = BIP44Path::from_string("m/44'/461'/0/0/0").unwrap();
+        assert_eq!(
+            xpub.derive_child(0).unwrap().public_key(),
+            master.derive_bip44(&path).unwrap().public_key()
+        );
+
+        assert!(xpub.derive_child(HARDENED_BIT).is_err());
+    }
+
     #[test]
     fn create_derive_path() {
         let path_string = "m/44'/461'/0/0/0";
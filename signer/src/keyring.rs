@@ -0,0 +1,47 @@
+use fvm_shared::address::Address;
+use fvm_shared::message::Message;
+
+use extras::signed_message::ref_fvm::SignedMessage;
+
+use crate::error::SignerError;
+use crate::{transaction_sign, PrivateKey};
+
+/// Collection of private keys indexed by their address
+#[derive(Default)]
+pub struct Keyring {
+    keys: Vec<(Address, PrivateKey)>,
+}
+
+impl Keyring {
+    pub fn new() -> Self {
+        Keyring { keys: Vec::new() }
+    }
+
+    /// Add a private key to the keyring. An existing key for the same address is replaced.
+    pub fn add(&mut self, address: Address, private_key: PrivateKey) {
+        self.keys.retain(|(a, _)| a.payload() != address.payload());
+        self.keys.push((address, private_key));
+    }
+
+    /// Return the private key matching the address, whatever the address network is.
+    pub fn get(&self, address: &Address) -> Option<&PrivateKey> {
+        self.keys
+            .iter()
+            .find(|(a, _)| a.payload() == address.payload())
+            .map(|(_, private_key)| private_key)
+    }
+
+    /// Sign a message with the private key matching the message `from` address.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - an unsigned filecoin message
+    ///
+    pub fn sign_by_address(&self, message: &Message) -> Result<SignedMessage, SignerError> {
+        let private_key = self
+            .get(&message.from)
+            .ok_or_else(|| SignerError::KeyNotFound(message.from.to_string()))?;
+
+        transaction_sign(message, private_key)
+    }
+}
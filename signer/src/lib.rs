@@ -8,35 +8,48 @@ use bip39::{Language, MnemonicType, Seed};
 use bls_signatures::Serialize;
 use fvm_shared::crypto::signature::{Signature, SignatureType};
 use fvm_shared::message::Message;
-use libsecp256k1::util::{COMPRESSED_PUBLIC_KEY_SIZE, SECRET_KEY_SIZE, SIGNATURE_SIZE};
-use num_traits::FromPrimitive;
+use hmac_drbg::HmacDRBG;
+use libsecp256k1::curve::Scalar;
+use libsecp256k1::util::{
+    COMPRESSED_PUBLIC_KEY_SIZE, FULL_PUBLIC_KEY_SIZE, SECRET_KEY_SIZE, SIGNATURE_SIZE,
+};
+use num_traits::{FromPrimitive, Signed};
 use rayon::prelude::*;
+use sha2::digest::generic_array::typenum::U32;
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
 use zx_bip44::BIP44Path;
 
 use cid::Cid;
 use fil_actor_init::{ExecParams, Method as MethodInit};
 use fil_actor_multisig as multisig;
 use fil_actor_paych as paych;
+use fil_actor_verifreg as verifreg;
 use fvm_ipld_encoding::{from_slice, to_vec, Cbor, RawBytes};
 use fvm_shared::address::{Address, Network, Protocol};
+use fvm_shared::clock::ChainEpoch;
 
+use bls12_381::G2Projective;
 use bls_signatures::PublicKey as BLSPublicKey;
 use libsecp256k1::PublicKey as SECP256K1PublicKey;
 
+use extras::datacap;
 use extras::signed_message::ref_fvm::SignedMessage;
 use regex::bytes::Regex;
 
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::MethodNum;
 
-use crate::api::{MessageParams, MessageTx, MessageTxAPI, MessageTxNetwork};
+use crate::api::{ActorType, MessageParams, MessageTx, MessageTxAPI, MessageTxNetwork};
 use crate::error::SignerError;
-use crate::extended_key::ExtendedSecretKey;
+use crate::extended_key::{ExtendedPublicKey, ExtendedSecretKey};
 use crate::multisig_deprecated::ConstructorParamsV1;
 
 pub mod api;
 pub mod error;
 pub mod extended_key;
+pub mod keyring;
 pub mod multisig_deprecated;
 pub mod utils;
 
@@ -44,8 +57,25 @@ pub mod utils;
 pub struct Mnemonic(pub String);
 
 pub const SIGNATURE_RECOVERY_SIZE: usize = SIGNATURE_SIZE + 1;
-
-/// Private key buffer
+pub const BLS_SIGNATURE_SIZE: usize = 96;
+pub const BLS_PUBLIC_KEY_SIZE: usize = 48;
+
+/// EIP-155 chain id of Filecoin mainnet
+const FILECOIN_MAINNET_CHAIN_ID: u64 = 314;
+/// EIP-155 chain id of the Filecoin calibration testnet
+const FILECOIN_CALIBRATION_CHAIN_ID: u64 = 314_159;
+/// Type byte of EIP-1559 transactions
+const EIP1559_TX_TYPE: u8 = 0x02;
+/// EVM actor `InvokeContract` method number (FRC-42)
+pub const EVM_INVOKE_CONTRACT_METHOD: MethodNum = 3_844_450_837;
+
+/// Private key buffer, wiped from memory when dropped
+///
+/// The intermediate buffers of the key conversions (decoded strings, derivation HMAC outputs)
+/// are wiped too. The copies made inside `libsecp256k1::SecretKey` (a `Copy` type),
+/// `bls_signatures::PrivateKey` and the HMAC states can't be reached and are not wiped.
+#[derive(Zeroize)]
+#[zeroize(drop)]
 pub struct PrivateKey(pub [u8; SECRET_KEY_SIZE]);
 
 pub enum PublicKey {
@@ -61,26 +91,205 @@ impl PublicKey {
             PublicKey::BLSPublicKey(pk) => pk.as_bytes(),
         }
     }
+
+    /// Return the 33 bytes compressed form of a secp256k1 public key
+    pub fn to_compressed(&self) -> Result<PublicKeyCompressed, SignerError> {
+        match self {
+            PublicKey::SECP256K1PublicKey(pk) => Ok(PublicKeyCompressed(pk.serialize_compressed())),
+            PublicKey::BLSPublicKey(_) => Err(SignerError::GenericString(
+                "BLS public keys have no compressed form".to_string(),
+            )),
+        }
+    }
+}
+
+/// Parse a public key, the type is detected from its length: 33 or 65 bytes for secp256k1
+/// (compressed or uncompressed) and 48 bytes for BLS
+impl TryFrom<&[u8]> for PublicKey {
+    type Error = SignerError;
+
+    fn try_from(buffer: &[u8]) -> Result<Self, Self::Error> {
+        match buffer.len() {
+            COMPRESSED_PUBLIC_KEY_SIZE | FULL_PUBLIC_KEY_SIZE => Ok(PublicKey::SECP256K1PublicKey(
+                SECP256K1PublicKey::parse_slice(buffer, None)?,
+            )),
+            BLS_PUBLIC_KEY_SIZE => Ok(PublicKey::BLSPublicKey(BLSPublicKey::from_bytes(buffer)?)),
+            got => Err(SignerError::GenericString(format!(
+                "Invalid public key length: {}",
+                got
+            ))),
+        }
+    }
+}
+
+impl PartialEq for PublicKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_vec() == other.to_vec()
+    }
 }
 
 /// Compressed public key buffer
 pub struct PublicKeyCompressed(pub [u8; COMPRESSED_PUBLIC_KEY_SIZE]);
 
+impl PublicKeyCompressed {
+    /// Parse the compressed buffer back into a secp256k1 public key
+    pub fn to_public_key(&self) -> Result<PublicKey, SignerError> {
+        let public_key = SECP256K1PublicKey::parse_compressed(&self.0)?;
+        Ok(PublicKey::SECP256K1PublicKey(public_key))
+    }
+}
+
+impl TryFrom<&[u8]> for PublicKeyCompressed {
+    type Error = SignerError;
+
+    fn try_from(buffer: &[u8]) -> Result<Self, Self::Error> {
+        let compressed = <[u8; COMPRESSED_PUBLIC_KEY_SIZE]>::try_from(buffer).map_err(|_| {
+            SignerError::InvalidKeyLength {
+                expected: COMPRESSED_PUBLIC_KEY_SIZE,
+                got: buffer.len(),
+            }
+        })?;
+        Ok(PublicKeyCompressed(compressed))
+    }
+}
+
 /// Extended key structure
 pub struct ExtendedKey {
     pub private_key: PrivateKey,
     pub public_key: PublicKey,
     pub address: String,
+    chain_code: Option<[u8; 32]>,
 }
 
 #[cfg(feature = "with-ffi-support")]
 ffi_support::implement_into_ffi_by_pointer!(ExtendedKey);
 
+/// Public information about a private key
+pub struct KeyInfo {
+    pub sig_type: SignatureType,
+    pub public_key: PublicKey,
+    pub address: String,
+}
+
+impl ExtendedKey {
+    /// BIP32 chain code, only known for keys derived from a seed
+    pub fn chain_code(&self) -> Option<[u8; 32]> {
+        self.chain_code
+    }
+
+    /// Return the extended public key (public key and chain code) to derive watch-only
+    /// non-hardened child addresses. Only available for secp256k1 keys derived from a seed.
+    pub fn neuter(&self) -> Result<ExtendedPublicKey, SignerError> {
+        let chain_code = self.chain_code.ok_or(SignerError::MissingChainCode)?;
+
+        match &self.public_key {
+            PublicKey::SECP256K1PublicKey(public_key) => {
+                ExtendedPublicKey::new(*public_key, &chain_code)
+            }
+            PublicKey::BLSPublicKey(_) => Err(SignerError::UnknownSigningProtocol),
+        }
+    }
+}
+
+/// Extended keys are equal when they have the same address and public key
+impl PartialEq for ExtendedKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.address == other.address && self.public_key == other.public_key
+    }
+}
+
+/// JSON layout of an `ExtendedKey`:
+///
+/// * `private_key` - base64 encoded private key
+/// * `public_key` - hex encoded public key (65 bytes secp256k1 uncompressed or 48 bytes BLS)
+/// * `address` - address string
+/// * `chain_code` - hex encoded BIP32 chain code, `null` if unknown
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ExtendedKeyJson {
+    private_key: String,
+    public_key: String,
+    address: String,
+    chain_code: Option<String>,
+}
+
+impl serde::Serialize for ExtendedKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut json = ExtendedKeyJson {
+            private_key: base64::encode(&self.private_key.0),
+            public_key: hex::encode(self.public_key.to_vec()),
+            address: self.address.clone(),
+            chain_code: self.chain_code.map(hex::encode),
+        };
+        let result = json.serialize(serializer);
+        json.private_key.zeroize();
+
+        result
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ExtendedKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let json = ExtendedKeyJson::deserialize(deserializer)?;
+
+        let private_key = PrivateKey::try_from(json.private_key).map_err(D::Error::custom)?;
+        let public_key_bytes = hex::decode(&json.public_key).map_err(D::Error::custom)?;
+        let public_key = PublicKey::try_from(&public_key_bytes[..]).map_err(D::Error::custom)?;
+        let chain_code = match json.chain_code {
+            Some(chain_code) => {
+                let chain_code = hex::decode(&chain_code).map_err(D::Error::custom)?;
+                Some(<[u8; 32]>::try_from(&chain_code[..]).map_err(D::Error::custom)?)
+            }
+            None => None,
+        };
+
+        Ok(ExtendedKey {
+            private_key,
+            public_key,
+            address: json.address,
+            chain_code,
+        })
+    }
+}
+
+/// Private keys comparison is done in constant time to avoid timing leaks
+impl PartialEq for PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0[..].ct_eq(&other.0[..]).into()
+    }
+}
+
+impl Eq for PrivateKey {}
+
 impl TryFrom<String> for PrivateKey {
     type Error = SignerError;
 
-    fn try_from(s: String) -> Result<PrivateKey, Self::Error> {
-        let v = base64::decode(&s)?;
+    /// Surrounding whitespace is ignored, both the standard and URL-safe alphabets are accepted,
+    /// with or without padding.
+    fn try_from(mut s: String) -> Result<PrivateKey, Self::Error> {
+        let v = [
+            base64::STANDARD,
+            base64::STANDARD_NO_PAD,
+            base64::URL_SAFE,
+            base64::URL_SAFE_NO_PAD,
+        ]
+        .iter()
+        .find_map(|config| base64::decode_config(s.trim(), *config).ok());
+        s.zeroize();
+
+        let v = v.ok_or_else(|| {
+            SignerError::InvalidBase64Key(
+                "expected the 32 bytes key encoded in base64 (standard or URL-safe alphabet)"
+                    .to_string(),
+            )
+        })?;
 
         PrivateKey::try_from(v)
     }
@@ -89,12 +298,18 @@ impl TryFrom<String> for PrivateKey {
 impl TryFrom<Vec<u8>> for PrivateKey {
     type Error = SignerError;
 
-    fn try_from(v: Vec<u8>) -> Result<PrivateKey, Self::Error> {
+    fn try_from(mut v: Vec<u8>) -> Result<PrivateKey, Self::Error> {
         if v.len() != SECRET_KEY_SIZE {
-            return Err(SignerError::GenericString("Invalid Key Length".to_string()));
+            let got = v.len();
+            v.as_mut_slice().zeroize();
+            return Err(SignerError::InvalidKeyLength {
+                expected: SECRET_KEY_SIZE,
+                got,
+            });
         }
         let mut sk = PrivateKey([0; SECRET_KEY_SIZE]);
         sk.0.copy_from_slice(&v[..SECRET_KEY_SIZE]);
+        v.as_mut_slice().zeroize();
         Ok(sk)
     }
 }
@@ -125,6 +340,66 @@ pub fn key_generate_mnemonic() -> Result<Mnemonic, SignerError> {
     Ok(Mnemonic(mnemonic.to_string()))
 }
 
+/// Generates a random mnemonic (English) with the given number of words (12, 15, 18, 21 or 24)
+///
+/// # Arguments
+///
+/// * `words` - The number of words of the mnemonic
+pub fn key_generate_mnemonic_with_length(words: usize) -> Result<Mnemonic, SignerError> {
+    key_generate_mnemonic_lang(words, "en")
+}
+
+/// Generates a random mnemonic with the given number of words (12, 15, 18, 21 or 24) and language
+///
+/// # Arguments
+///
+/// * `words` - The number of words of the mnemonic
+/// * `language_code` - The language code of the mnemonic words (e.g "en", "ja" or "zh-hans")
+pub fn key_generate_mnemonic_lang(
+    words: usize,
+    language_code: &str,
+) -> Result<Mnemonic, SignerError> {
+    let lang = Language::from_language_code(language_code)
+        .ok_or_else(|| SignerError::UnknownLanguageCode(language_code.to_string()))?;
+    let mnemonic_type = MnemonicType::for_word_count(words)
+        .map_err(|_| SignerError::InvalidMnemonicWordCount(words))?;
+
+    let mnemonic = bip39::Mnemonic::new(mnemonic_type, lang);
+    Ok(Mnemonic(mnemonic.to_string()))
+}
+
+/// Check that a mnemonic is valid: word count, words from the language wordlist and checksum
+///
+/// # Arguments
+///
+/// * `mnemonic` - A string containing the mnemonic words
+/// * `language_code` - The language code for the mnemonic (e.g "en" if english words are used)
+pub fn key_validate_mnemonic(mnemonic: &str, language_code: &str) -> Result<(), SignerError> {
+    parse_mnemonic(mnemonic, language_code).map(|_| ())
+}
+
+/// Parse a mnemonic, reporting the validation failures with their own error variant
+pub(crate) fn parse_mnemonic(
+    mnemonic: &str,
+    language_code: &str,
+) -> Result<bip39::Mnemonic, SignerError> {
+    let lang = Language::from_language_code(language_code)
+        .ok_or_else(|| SignerError::UnknownLanguageCode(language_code.to_string()))?;
+
+    let word_count = mnemonic.split_whitespace().count();
+    if !matches!(word_count, 12 | 15 | 18 | 21 | 24) {
+        return Err(SignerError::InvalidMnemonicWordCount(word_count));
+    }
+
+    bip39::Mnemonic::from_phrase(mnemonic, lang).map_err(|err| {
+        match err.downcast_ref::<bip39::ErrorKind>() {
+            Some(bip39::ErrorKind::InvalidWord) => SignerError::InvalidMnemonicWord,
+            Some(bip39::ErrorKind::InvalidChecksum) => SignerError::InvalidMnemonicChecksum,
+            _ => SignerError::GenericString(err.to_string()),
+        }
+    })
+}
+
 fn derive_extended_secret_key(seed: &[u8], path: &str) -> Result<ExtendedSecretKey, SignerError> {
     let master = ExtendedSecretKey::try_from(seed)?;
     let bip44_path = BIP44Path::from_string(path)?;
@@ -133,27 +408,50 @@ fn derive_extended_secret_key(seed: &[u8], path: &str) -> Result<ExtendedSecretK
     Ok(esk)
 }
 
+/// Derive the BIP32 master key of a mnemonic
+fn master_key_from_mnemonic(
+    mnemonic: &str,
+    password: &str,
+    language_code: &str,
+) -> Result<ExtendedSecretKey, SignerError> {
+    let mnemonic = parse_mnemonic(mnemonic, language_code)?;
+    let seed = Seed::new(&mnemonic, password);
+
+    Ok(ExtendedSecretKey::try_from(seed.as_bytes())?)
+}
+
 fn derive_extended_secret_key_from_mnemonic(
     mnemonic: &str,
     path: &str,
     password: &str,
     language_code: &str,
 ) -> Result<ExtendedSecretKey, SignerError> {
-    let lang = Language::from_language_code(language_code);
+    let master = master_key_from_mnemonic(mnemonic, password, language_code)?;
+    let bip44_path = BIP44Path::from_string(path)?;
 
-    match lang {
-        Some(l) => {
-            let mnemonic = bip39::Mnemonic::from_phrase(mnemonic, l)
-                .map_err(|err| SignerError::GenericString(err.to_string()))?;
+    Ok(master.derive_bip44(&bip44_path)?)
+}
 
-            let seed = Seed::new(&mnemonic, password);
+/// Derive the key of a base path without the address index (e.g "m/44'/461'/0/0"). Also return
+/// the path of the first address under it.
+fn derive_account_key_from_mnemonic(
+    mnemonic: &str,
+    base_path: &str,
+    password: &str,
+    language_code: &str,
+) -> Result<(ExtendedSecretKey, BIP44Path), SignerError> {
+    let master = master_key_from_mnemonic(mnemonic, password, language_code)?;
 
-            derive_extended_secret_key(seed.as_bytes(), path)
-        }
-        None => Err(SignerError::GenericString(
-            "Unknown language code".to_string(),
-        )),
-    }
+    // Parse the path of the first account to validate the base path
+    let bip44_path = BIP44Path::from_string(&format!("{}/0", base_path.trim_end_matches('/')))?;
+
+    let account = master
+        .derive_child_key(bip44_path.0[0])?
+        .derive_child_key(bip44_path.0[1])?
+        .derive_child_key(bip44_path.0[2])?
+        .derive_child_key(bip44_path.0[3])?;
+
+    Ok((account, bip44_path))
 }
 
 /// Returns a public key, private key and address given a mnemonic, derivation path and a password (support chinese mnemonic)
@@ -185,6 +483,7 @@ pub fn key_derive(
         private_key: PrivateKey(esk.secret_key()),
         public_key: PublicKey::SECP256K1PublicKey(SECP256K1PublicKey::parse(&esk.public_key())?),
         address: address.to_string(),
+        chain_code: Some(esk.chain_code()),
     })
 }
 
@@ -211,9 +510,237 @@ pub fn key_derive_from_seed(seed: &[u8], path: &str) -> Result<ExtendedKey, Sign
         private_key: PrivateKey(esk.secret_key()),
         public_key: PublicKey::SECP256K1PublicKey(SECP256K1PublicKey::parse(&esk.public_key())?),
         address: address.to_string(),
+        chain_code: Some(esk.chain_code()),
     })
 }
 
+/// Returns a BLS public key, private key and address given a mnemonic, derivation path and a password
+///
+/// The secp256k1 secret key derived at `path` is used as input key material for the BLS key
+/// generation, so that any path gives a valid BLS key.
+///
+/// # Arguments
+///
+/// * `mnemonic` - A string containing a 24-words English mnemonic
+/// * `path` - A string containing a derivation path
+/// * `password` - Password to decrypt seed, if none use and empty string (e.g "")
+/// * `language_code` - The language code for the mnemonic (e.g "en" if english words are used)
+pub fn key_derive_bls(
+    mnemonic: &str,
+    path: &str,
+    password: &str,
+    language_code: &str,
+) -> Result<ExtendedKey, SignerError> {
+    let esk = derive_extended_secret_key_from_mnemonic(mnemonic, path, password, language_code)?;
+
+    let sk = bls_signatures::PrivateKey::new(&*Zeroizing::new(esk.secret_key()));
+
+    let bip44_path = BIP44Path::from_string(path)?;
+
+    key_recover_bls(
+        &PrivateKey::try_from(sk.as_bytes())?,
+        bip44_path.is_testnet(),
+    )
+}
+
+/// Returns the extended keys of the first `count` addresses under a base path, deriving the
+/// master key from the mnemonic only once
+///
+/// # Arguments
+///
+/// * `mnemonic` - A string containing a 24-words English mnemonic
+/// * `base_path` - A derivation path without the address index (e.g "m/44'/461'/0/0")
+/// * `password` - Password to decrypt seed, if none use and empty string (e.g "")
+/// * `language_code` - The language code for the mnemonic (e.g "en" if english words are used)
+/// * `count` - The number of accounts to derive, starting at index 0
+pub fn key_derive_accounts(
+    mnemonic: &str,
+    base_path: &str,
+    password: &str,
+    language_code: &str,
+    count: u32,
+) -> Result<Vec<ExtendedKey>, SignerError> {
+    let (account, bip44_path) =
+        derive_account_key_from_mnemonic(mnemonic, base_path, password, language_code)?;
+
+    let network = if bip44_path.is_testnet() {
+        Network::Testnet
+    } else {
+        Network::Mainnet
+    };
+
+    (0..count)
+        .map(|index| {
+            let esk = account.derive_child_key(index)?;
+
+            let mut address = Address::new_secp256k1(esk.public_key().as_ref())?;
+            address.set_network(network);
+
+            Ok(ExtendedKey {
+                private_key: PrivateKey(esk.secret_key()),
+                public_key: PublicKey::SECP256K1PublicKey(SECP256K1PublicKey::parse(
+                    &esk.public_key(),
+                )?),
+                address: address.to_string(),
+                chain_code: Some(esk.chain_code()),
+            })
+        })
+        .collect()
+}
+
+/// Returns the extended public key (public key and chain code) of a base path, to derive the
+/// addresses under it without the private key (watch-only wallets)
+///
+/// # Arguments
+///
+/// * `mnemonic` - A string containing the mnemonic words
+/// * `path` - A derivation path without the address index (e.g "m/44'/461'/0/0")
+/// * `password` - Password to decrypt seed, if none use and empty string (e.g "")
+/// * `language_code` - The language code for the mnemonic (e.g "en" if english words are used)
+pub fn key_derive_xpub(
+    mnemonic: &str,
+    path: &str,
+    password: &str,
+    language_code: &str,
+) -> Result<ExtendedPublicKey, SignerError> {
+    let (account, _) = derive_account_key_from_mnemonic(mnemonic, path, password, language_code)?;
+
+    Ok(account.neuter())
+}
+
+/// Returns the keys of the used addresses found by deriving sequential indices under a base path,
+/// stopping after `gap_limit` consecutive unused addresses (BIP44 account discovery)
+///
+/// # Arguments
+///
+/// * `seed` - A seed as bytes array
+/// * `base_path` - A derivation path without the address index (e.g "m/44'/461'/0/0")
+/// * `gap_limit` - The number of consecutive unused addresses after which the scan stops
+/// * `is_used` - A predicate telling if an address has been used
+///
+pub fn scan_addresses(
+    seed: &[u8],
+    base_path: &str,
+    gap_limit: u32,
+    is_used: impl Fn(&str) -> bool,
+) -> Result<Vec<ExtendedKey>, SignerError> {
+    let mut used_keys = Vec::new();
+    let mut unused_count = 0;
+    let mut index: u32 = 0;
+
+    while unused_count < gap_limit {
+        let path = format!("{}/{}", base_path.trim_end_matches('/'), index);
+        let extended_key = key_derive_from_seed(seed, &path)?;
+
+        if is_used(&extended_key.address) {
+            used_keys.push(extended_key);
+            unused_count = 0;
+        } else {
+            unused_count += 1;
+        }
+
+        index = index
+            .checked_add(1)
+            .ok_or_else(|| SignerError::GenericString("Address index overflow".to_string()))?;
+    }
+
+    Ok(used_keys)
+}
+
+/// Returns the addresses derived from a mnemonic for each derivation path, in the same order.
+/// The master key is derived only once and the paths are derived in parallel. Only the
+/// addresses are returned so that no private key is exposed while scanning for used accounts.
+///
+/// # Arguments
+///
+/// * `mnemonic` - A string containing the mnemonic words
+/// * `password` - Password to decrypt seed, if none use and empty string (e.g "")
+/// * `language_code` - The language code for the mnemonic (e.g "en" if english words are used)
+/// * `paths` - The derivation paths to scan
+///
+pub fn scan_mnemonic_addresses(
+    mnemonic: &str,
+    password: &str,
+    language_code: &str,
+    paths: &[String],
+) -> Result<Vec<String>, SignerError> {
+    let master = master_key_from_mnemonic(mnemonic, password, language_code)?;
+
+    paths
+        .par_iter()
+        .map(|path| {
+            let bip44_path = BIP44Path::from_string(path)?;
+            let esk = master.derive_bip44(&bip44_path)?;
+
+            let mut address = Address::new_secp256k1(esk.public_key().as_ref())?;
+            address.set_network(if bip44_path.is_testnet() {
+                Network::Testnet
+            } else {
+                Network::Mainnet
+            });
+
+            Ok(address.to_string())
+        })
+        .collect()
+}
+
+/// Returns the extended key with its mainnet address and the testnet form of the address,
+/// given a mnemonic, derivation path and a password. The key is derived only once.
+///
+/// # Arguments
+///
+/// * `mnemonic` - A string containing the mnemonic words
+/// * `path` - A string containing a derivation path
+/// * `password` - Password to decrypt seed, if none use and empty string (e.g "")
+/// * `language_code` - The language code for the mnemonic (e.g "en" if english words are used)
+pub fn key_derive_all_networks(
+    mnemonic: &str,
+    path: &str,
+    password: &str,
+    language_code: &str,
+) -> Result<(ExtendedKey, String), SignerError> {
+    let esk = derive_extended_secret_key_from_mnemonic(mnemonic, path, password, language_code)?;
+
+    let mut address = Address::new_secp256k1(esk.public_key().as_ref())?;
+
+    address.set_network(Network::Mainnet);
+    let mainnet_address = address.to_string();
+
+    address.set_network(Network::Testnet);
+    let testnet_address = address.to_string();
+
+    let extended_key = ExtendedKey {
+        private_key: PrivateKey(esk.secret_key()),
+        public_key: PublicKey::SECP256K1PublicKey(SECP256K1PublicKey::parse(&esk.public_key())?),
+        address: mainnet_address,
+        chain_code: Some(esk.chain_code()),
+    };
+
+    Ok((extended_key, testnet_address))
+}
+
+/// Verify that a mnemonic derives to the expected address. Return a boolean.
+///
+/// # Arguments
+///
+/// * `mnemonic` - A string containing the mnemonic words
+/// * `path` - A string containing a derivation path
+/// * `password` - Password to decrypt seed, if none use and empty string (e.g "")
+/// * `language_code` - The language code for the mnemonic (e.g "en" if english words are used)
+/// * `expected_address` - The address the mnemonic should produce
+///
+pub fn verify_mnemonic_address(
+    mnemonic: &str,
+    path: &str,
+    password: &str,
+    language_code: &str,
+    expected_address: &str,
+) -> Result<bool, SignerError> {
+    let extended_key = key_derive(mnemonic, path, password, language_code)?;
+
+    Ok(extended_key.address == expected_address)
+}
+
 /// Get extended key from private key
 ///
 /// # Arguments
@@ -236,9 +763,31 @@ pub fn key_recover(private_key: &PrivateKey, testnet: bool) -> Result<ExtendedKe
         private_key: PrivateKey(secret_key.serialize()),
         public_key: PublicKey::SECP256K1PublicKey(public_key),
         address: address.to_string(),
+        chain_code: None,
     })
 }
 
+/// Get a deterministic secp256k1 extended key from an integer seed.
+///
+/// **For tests and examples only.** The key is trivially guessable from the seed
+/// and must never be used to hold funds.
+///
+/// # Arguments
+///
+/// * `seed` - Any integer, the same seed always gives the same key
+/// * `testnet` - specify the network, `true` if testnet else `false` for mainnet
+///
+pub fn key_from_test_seed(seed: u64, testnet: bool) -> Result<ExtendedKey, SignerError> {
+    let mut secret = utils::blake2b_256(&seed.to_be_bytes());
+
+    // Rehash in the (very unlikely) case the digest isn't a valid secret key
+    while libsecp256k1::SecretKey::parse(&secret).is_err() {
+        secret = utils::blake2b_256(&secret);
+    }
+
+    key_recover(&PrivateKey(secret), testnet)
+}
+
 /// Get extended key from BLS private key
 ///
 /// # Arguments
@@ -260,54 +809,375 @@ pub fn key_recover_bls(
         address.set_network(Network::Mainnet);
     }
 
+    let mut sk_bytes = sk.as_bytes();
     let mut secret_key = PrivateKey([0; SECRET_KEY_SIZE]);
-    secret_key.0.copy_from_slice(&sk.as_bytes());
+    secret_key.0.copy_from_slice(&sk_bytes);
+    sk_bytes.as_mut_slice().zeroize();
 
     Ok(ExtendedKey {
         private_key: secret_key,
         public_key: PublicKey::BLSPublicKey(sk.public_key()),
         address: address.to_string(),
+        chain_code: None,
     })
 }
 
-/// Serialize a transaction and return a CBOR hexstring.
+/// Get extended key from BLS private key, the network is taken from a BIP44 derivation path
+/// (coin type 1 for testnet) like the mnemonic based functions
 ///
 /// # Arguments
 ///
-/// * `message` - a filecoin message (aka transaction)
+/// * `private_key` - A `bls_signatures::PrivateKey`
+/// * `path` - A string containing a derivation path (e.g "m/44'/1'/0/0/0")
 ///
-pub fn transaction_serialize(message: &Message) -> Result<Vec<u8>, SignerError> {
-    let message_cbor = message.marshal_cbor()?;
-    Ok(message_cbor)
+pub fn key_recover_bls_from_path(
+    private_key: &PrivateKey,
+    path: &str,
+) -> Result<ExtendedKey, SignerError> {
+    let bip44_path = BIP44Path::from_string(path)?;
+
+    key_recover_bls(private_key, bip44_path.is_testnet())
 }
 
-/// Parse a CBOR hextring into a filecoin transaction (signed or unsigned).
+/// Key info as exported by `lotus wallet export`
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LotusKeyInfo {
+    #[serde(rename = "Type")]
+    key_type: String,
+    private_key: String,
+}
+
+/// Export a key in the `lotus wallet export` format: the hex encoded JSON key info
 ///
 /// # Arguments
 ///
-/// * `hexstring` - the cbor hexstring to parse
-/// * `testnet` - boolean value `true` if testnet or `false` for mainnet
+/// * `key` - The extended key to export
 ///
-pub fn transaction_parse(cbor: &[u8], testnet: bool) -> Result<MessageTxAPI, SignerError> {
-    let message: MessageTx = from_slice(cbor)?;
-
-    let message_tx_with_network = MessageTxNetwork {
-        message_tx: MessageTxAPI::from(message),
-        testnet,
+pub fn export_key_lotus(key: &ExtendedKey) -> Result<String, SignerError> {
+    let key_type = match key.public_key {
+        PublicKey::SECP256K1PublicKey(_) => "secp256k1",
+        PublicKey::BLSPublicKey(_) => "bls",
     };
 
-    let parsed_message = MessageTxAPI::try_from(message_tx_with_network)?;
+    let mut key_info = LotusKeyInfo {
+        key_type: key_type.to_string(),
+        private_key: base64::encode(&key.private_key.0),
+    };
+    let json = serde_json::to_string(&key_info)
+        .map(Zeroizing::new)
+        .map_err(|err| SignerError::GenericString(err.to_string()));
+    key_info.private_key.zeroize();
 
-    Ok(parsed_message)
+    Ok(hex::encode(&*json?))
 }
 
-fn transaction_sign_secp56k1_raw(
-    message: &Message,
-    private_key: &PrivateKey,
-) -> Result<Signature, SignerError> {
+/// Import a key exported with `lotus wallet export`. The address is a mainnet address.
+///
+/// # Arguments
+///
+/// * `exported_key` - The hex encoded JSON key info
+///
+pub fn import_key_lotus(exported_key: &str) -> Result<ExtendedKey, SignerError> {
+    let json = Zeroizing::new(hex::decode(exported_key.trim())?);
+    let key_info: LotusKeyInfo =
+        serde_json::from_slice(&json).map_err(|err| SignerError::GenericString(err.to_string()))?;
+
+    let private_key = PrivateKey::try_from(key_info.private_key)?;
+
+    match key_info.key_type.as_str() {
+        "secp256k1" => key_recover(&private_key, false),
+        "bls" => key_recover_bls(&private_key, false),
+        _ => Err(SignerError::UnknownSigningProtocol),
+    }
+}
+
+/// Get the signing scheme, public key and address of a private key, checking that the key
+/// is valid for the scheme. The address is a mainnet address.
+///
+/// # Arguments
+///
+/// * `private_key` - A `PrivateKey`
+/// * `sig_type` - The signing scheme the key is meant for
+///
+pub fn key_info(private_key: &PrivateKey, sig_type: SignatureType) -> Result<KeyInfo, SignerError> {
+    let extended_key = match sig_type {
+        SignatureType::Secp256k1 => key_recover(private_key, false)?,
+        SignatureType::BLS => key_recover_bls(private_key, false)?,
+    };
+
+    Ok(KeyInfo {
+        sig_type,
+        public_key: extended_key.public_key,
+        address: extended_key.address,
+    })
+}
+
+/// Serialize a transaction and return a CBOR hexstring.
+///
+/// # Arguments
+///
+/// * `message` - a filecoin message (aka transaction)
+///
+pub fn transaction_serialize(message: &Message) -> Result<Vec<u8>, SignerError> {
+    let message_cbor = message.marshal_cbor()?;
+    Ok(message_cbor)
+}
+
+/// Parse the first filecoin transaction (signed or unsigned) of a CBOR buffer.
+/// Return the transaction and the number of bytes consumed, to iterate over back-to-back messages.
+///
+/// # Arguments
+///
+/// * `cbor` - the cbor buffer starting with a transaction
+/// * `testnet` - boolean value `true` if testnet or `false` for mainnet
+///
+pub fn transaction_parse_prefix(
+    cbor: &[u8],
+    testnet: bool,
+) -> Result<(MessageTxAPI, usize), SignerError> {
+    let mut deserializer = serde_cbor::Deserializer::from_slice(cbor);
+    let message: MessageTx = serde::Deserialize::deserialize(&mut deserializer).map_err(|err| {
+        if err.is_eof() {
+            SignerError::IncompleteCbor
+        } else {
+            SignerError::CBOR(err)
+        }
+    })?;
+    let consumed = deserializer.byte_offset();
+
+    if let MessageTx::SignedMessage(signed_message) = &message {
+        check_signature_length(&signed_message.signature)?;
+    }
+
+    let message_tx_with_network = MessageTxNetwork {
+        message_tx: message.into(),
+        testnet,
+    };
+
+    let parsed_message = MessageTxAPI::try_from(message_tx_with_network)?;
+
+    Ok((parsed_message, consumed))
+}
+
+fn check_signature_length(signature: &Signature) -> Result<(), SignerError> {
+    let expected = match signature.sig_type {
+        SignatureType::Secp256k1 => SIGNATURE_RECOVERY_SIZE,
+        SignatureType::BLS => BLS_SIGNATURE_SIZE,
+    };
+
+    if signature.bytes.len() != expected {
+        return Err(SignerError::InvalidSignatureLength(
+            signature.sig_type,
+            expected,
+            signature.bytes.len(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Parse a CBOR hextring into a filecoin transaction (signed or unsigned).
+///
+/// The network is not encoded in the CBOR payload: addresses are serialized as protocol byte
+/// and payload only, so the network can't be detected and has to be given.
+///
+/// Gas limit, fee cap and premium must be non-negative and the fee cap must cover the premium,
+/// otherwise `SignerError::InvalidGasParams` is returned. See `transaction_parse_unchecked`.
+///
+/// # Arguments
+///
+/// * `hexstring` - the cbor hexstring to parse
+/// * `testnet` - boolean value `true` if testnet or `false` for mainnet
+///
+pub fn transaction_parse(cbor: &[u8], testnet: bool) -> Result<MessageTxAPI, SignerError> {
+    let message: MessageTx = from_slice(cbor)?;
+
+    if let MessageTx::SignedMessage(signed_message) = &message {
+        check_signature_length(&signed_message.signature)?;
+    }
+
+    let message_tx_with_network = MessageTxNetwork {
+        message_tx: message.into(),
+        testnet,
+    };
+
+    let parsed_message = MessageTxAPI::try_from(message_tx_with_network)?;
+
+    Ok(parsed_message)
+}
+
+/// Parse a CBOR filecoin transaction (signed or unsigned) without checking the gas values.
+///
+/// `transaction_parse` rejects negative gas values and a premium above the fee cap, use this
+/// only to read transactions that are known to break those rules.
+///
+/// # Arguments
+///
+/// * `cbor` - the cbor transaction to parse
+/// * `testnet` - boolean value `true` if testnet or `false` for mainnet
+///
+pub fn transaction_parse_unchecked(
+    cbor: &[u8],
+    testnet: bool,
+) -> Result<MessageTxAPI, SignerError> {
+    let message: MessageTx = from_slice(cbor)?;
+
+    if let MessageTx::SignedMessage(signed_message) = &message {
+        check_signature_length(&signed_message.signature)?;
+    }
+
+    let message_tx_with_network = MessageTxNetwork {
+        message_tx: message.into(),
+        testnet,
+    };
+
+    Ok(message_tx_with_network.into_unchecked())
+}
+
+/// Parse a CBOR filecoin transaction (signed or unsigned), rejecting non canonical encodings.
+///
+/// The parsed transaction is serialized again and must give back the exact input bytes, so that
+/// the CID of the parsed transaction is the CID of the input.
+///
+/// # Arguments
+///
+/// * `cbor` - the cbor transaction to parse
+/// * `testnet` - boolean value `true` if testnet or `false` for mainnet
+///
+pub fn transaction_parse_strict(cbor: &[u8], testnet: bool) -> Result<MessageTxAPI, SignerError> {
+    let message = transaction_parse(cbor, testnet)?;
+
+    let serialized = match &message {
+        MessageTxAPI::Message(message) => transaction_serialize(message)?,
+        MessageTxAPI::SignedMessage(signed_message) => signed_message.marshal_cbor()?,
+    };
+
+    if serialized != cbor {
+        return Err(SignerError::NonCanonicalCbor);
+    }
+
+    Ok(message)
+}
+
+/// Parse a CBOR filecoin transaction (signed or unsigned) and render it as pretty JSON,
+/// using the Lotus JSON message format (including the `CID`).
+///
+/// # Arguments
+///
+/// * `cbor` - the cbor transaction to inspect
+/// * `testnet` - boolean value `true` if testnet or `false` for mainnet
+///
+pub fn transaction_to_json(cbor: &[u8], testnet: bool) -> Result<String, SignerError> {
+    let json = match transaction_parse(cbor, testnet)? {
+        MessageTxAPI::Message(message) => {
+            serde_json::to_string_pretty(&LotusMessageJson::new(&message)?)
+        }
+        MessageTxAPI::SignedMessage(signed_message) => {
+            serde_json::to_string_pretty(&LotusSignedMessageJson::new(&signed_message)?)
+        }
+    };
+
+    json.map_err(|err| SignerError::GenericString(err.to_string()))
+}
+
+/// Parse a CBOR unsigned filecoin transaction. Fails if the CBOR is a signed transaction.
+///
+/// # Arguments
+///
+/// * `cbor` - the cbor transaction to parse
+/// * `testnet` - boolean value `true` if testnet or `false` for mainnet
+///
+pub fn transaction_parse_unsigned(cbor: &[u8], testnet: bool) -> Result<Message, SignerError> {
+    match transaction_parse(cbor, testnet)? {
+        MessageTxAPI::Message(message) => Ok(message),
+        MessageTxAPI::SignedMessage(_) => Err(SignerError::UnexpectedSignedMessage),
+    }
+}
+
+/// Return the label/value pairs shown by the Filecoin Ledger app when reviewing a transaction,
+/// in the same order, so that a preview can be displayed before asking the device to sign.
+///
+/// # Arguments
+///
+/// * `message` - an unsigned filecoin message
+/// * `network` - the network used to render the addresses
+///
+pub fn ledger_display_fields(
+    message: &Message,
+    network: Network,
+) -> Result<Vec<(String, String)>, SignerError> {
+    let mut to = message.to;
+    to.set_network(network);
+    let mut from = message.from;
+    from.set_network(network);
+
+    let mut fields = vec![
+        ("To".to_string(), to.to_string()),
+        ("From".to_string(), from.to_string()),
+        ("Nonce".to_string(), message.sequence.to_string()),
+        (
+            "Value".to_string(),
+            format!("FIL {}", utils::format_fil(&message.value)),
+        ),
+        ("Gas Limit".to_string(), message.gas_limit.to_string()),
+        (
+            "Gas Fee Cap".to_string(),
+            format!("FIL {}", utils::format_fil(&message.gas_fee_cap)),
+        ),
+        (
+            "Gas Premium".to_string(),
+            format!("FIL {}", utils::format_fil(&message.gas_premium)),
+        ),
+        ("Method".to_string(), message.method_num.to_string()),
+    ];
+
+    if !message.params.bytes().is_empty() {
+        fields.push(("Params".to_string(), hex::encode(message.params.bytes())));
+    }
+
+    Ok(fields)
+}
+
+/// Return the message as the JSON expected by Lotus `GasEstimateMessageGas`, with the gas fields
+/// zeroed so that the node estimates them.
+///
+/// # Arguments
+///
+/// * `message` - an unsigned filecoin message
+///
+pub fn gas_estimate_payload(message: &Message) -> Result<String, SignerError> {
+    let message_api = MessageTxAPI::Message(Message {
+        gas_limit: 0,
+        gas_fee_cap: TokenAmount::from(0),
+        gas_premium: TokenAmount::from(0),
+        ..message.to_owned()
+    });
+
+    serde_json::to_string(&message_api).map_err(|err| SignerError::GenericString(err.to_string()))
+}
+
+fn transaction_sign_secp56k1_raw(
+    signing_bytes: &[u8],
+    private_key: &PrivateKey,
+) -> Result<Signature, SignerError> {
+    sign_secp256k1_digest(&utils::blake2b_256(signing_bytes), private_key)
+}
+
+/// Sign a 32 bytes digest with a secp256k1 key (RFC6979 deterministic nonce) and return a raw
+/// signature (RSV format, with the recovery id `v` being 0 or 1).
+///
+/// # Arguments
+///
+/// * `digest` - the 32 bytes digest to sign
+/// * `private_key` - a secp256k1 `PrivateKey`
+///
+pub fn sign_secp256k1_digest(
+    digest: &[u8; 32],
+    private_key: &PrivateKey,
+) -> Result<Signature, SignerError> {
     let secret_key = libsecp256k1::SecretKey::parse_slice(&private_key.0)?;
-    let message_digest =
-        libsecp256k1::Message::parse_slice(&utils::blake2b_256(&message.to_signing_bytes()))?;
+    let message_digest = libsecp256k1::Message::parse(digest);
 
     let (signature_rs, recovery_id) = libsecp256k1::sign(&message_digest, &secret_key);
 
@@ -317,95 +1187,761 @@ fn transaction_sign_secp56k1_raw(
 
     let signature = Signature::new_secp256k1(sig.to_vec());
 
-    Ok(signature)
+    Ok(signature)
+}
+
+/// Sign a message with a secp256k1 key, adding extra entropy to the RFC6979 nonce generation.
+/// Return a raw signature (RSV format). `transaction_sign_raw` stays fully deterministic.
+///
+/// # Arguments
+///
+/// * `message` - an unsigned filecoin message
+/// * `private_key` - a secp256k1 `PrivateKey`
+/// * `extra` - 32 bytes of additional data fed to RFC6979 (as in libsecp256k1 `ndata`)
+///
+pub fn transaction_sign_secp256k1_with_extra(
+    message: &Message,
+    private_key: &PrivateKey,
+    extra: &[u8; 32],
+) -> Result<Signature, SignerError> {
+    let digest = utils::blake2b_256(&message.to_signing_bytes());
+
+    sign_secp256k1_rfc6979(&digest, private_key, extra)
+}
+
+/// Sign a digest with the RFC6979 nonce generated from secret key || digest || `extra`. With an
+/// empty `extra` the signature is the one of `libsecp256k1::sign`.
+fn sign_secp256k1_rfc6979(
+    digest: &[u8; 32],
+    private_key: &PrivateKey,
+    extra: &[u8],
+) -> Result<Signature, SignerError> {
+    let message_digest = libsecp256k1::Message::parse(digest);
+
+    // Parse the key as a scalar directly, `libsecp256k1::SecretKey` is `Copy` and can't be wiped
+    let mut secret_scalar = Scalar::default();
+    let overflow = bool::from(secret_scalar.set_b32(&private_key.0));
+    if overflow || secret_scalar.is_zero() {
+        secret_scalar.clear();
+        return Err(libsecp256k1::Error::InvalidSecretKey.into());
+    }
+
+    // The DRBG state itself can't be wiped, `hmac-drbg` doesn't support it
+    let mut drbg = HmacDRBG::<Sha256>::new(&private_key.0, digest, extra);
+
+    let (sig_r, sig_s, recovery_id) = loop {
+        let mut generated = drbg.generate::<U32>(None);
+        let mut nonce_bytes = Zeroizing::new([0u8; 32]);
+        nonce_bytes.copy_from_slice(&generated);
+        generated.as_mut_slice().zeroize();
+
+        let mut nonce = Scalar::default();
+        let overflow = bool::from(nonce.set_b32(&nonce_bytes));
+
+        let result = if !overflow && !nonce.is_zero() {
+            libsecp256k1::ECMULT_GEN_CONTEXT
+                .sign_raw(&secret_scalar, &message_digest.0, &nonce)
+                .ok()
+        } else {
+            None
+        };
+        nonce.clear();
+
+        if let Some(result) = result {
+            break result;
+        }
+    };
+    secret_scalar.clear();
+
+    let mut sig = [0; 65];
+    sig[..32].copy_from_slice(&sig_r.b32());
+    sig[32..64].copy_from_slice(&sig_s.b32());
+    sig[64] = recovery_id;
+
+    let signature = Signature::new_secp256k1(sig.to_vec());
+
+    Ok(signature)
+}
+
+fn transaction_sign_bls_raw(
+    signing_bytes: &[u8],
+    private_key: &PrivateKey,
+) -> Result<Signature, SignerError> {
+    let sk = bls_signatures::PrivateKey::from_bytes(&private_key.0)?;
+    let sig = sk.sign(signing_bytes);
+    let signature = Signature::new_bls(sig.as_bytes());
+
+    Ok(signature)
+}
+
+/// Sign the signing bytes of a message (`Message::to_signing_bytes`) and return a raw signature
+/// (RSV format for secp256k1). Secp256k1 signs the blake2b digest of the bytes, BLS signs the bytes.
+///
+/// # Arguments
+///
+/// * `signing_bytes` - the signing bytes of an unsigned filecoin message
+/// * `private_key` - a `PrivateKey`
+/// * `sig_type` - the signing scheme to use
+///
+pub fn sign_signing_bytes(
+    signing_bytes: &[u8],
+    private_key: &PrivateKey,
+    sig_type: SignatureType,
+) -> Result<Signature, SignerError> {
+    match sig_type {
+        SignatureType::Secp256k1 => transaction_sign_secp56k1_raw(signing_bytes, private_key),
+        SignatureType::BLS => transaction_sign_bls_raw(signing_bytes, private_key),
+    }
+}
+
+/// Domain tag prepended (followed by the data length) to the arbitrary data signed by
+/// `sign_bytes`, as in FRC-102. Part of the stable API: changing it invalidates all signatures.
+pub const SIGNED_BYTES_PREFIX: &[u8] = b"\x19Filecoin Signed Message:\n";
+
+fn signed_bytes_payload(data: &[u8]) -> Vec<u8> {
+    let mut payload = SIGNED_BYTES_PREFIX.to_vec();
+    payload.extend_from_slice(data.len().to_string().as_bytes());
+    payload.extend_from_slice(data);
+    payload
+}
+
+/// Sign arbitrary data (e.g for off-chain authentication) and return a raw signature.
+///
+/// The data is prefixed with `"\x19Filecoin Signed Message:\n" || len(data)` before signing, so
+/// the signature can't be replayed as a transaction signature (a message CID can't start with
+/// this prefix). Secp256k1 signs the blake2b digest of the prefixed data, BLS signs it directly.
+///
+/// # Arguments
+///
+/// * `data` - the data to sign
+/// * `private_key` - a `PrivateKey`
+/// * `scheme` - the signing scheme to use
+///
+pub fn sign_bytes(
+    data: &[u8],
+    private_key: &PrivateKey,
+    scheme: SignatureType,
+) -> Result<Signature, SignerError> {
+    sign_signing_bytes(&signed_bytes_payload(data), private_key, scheme)
+}
+
+/// Verify a signature produced by `sign_bytes`. Return a boolean.
+///
+/// # Arguments
+///
+/// * `data` - the signed data
+/// * `signature` - RSV format signature or BLS signature
+/// * `signer` - the address of the signer
+///
+pub fn verify_bytes(
+    data: &[u8],
+    signature: &Signature,
+    signer: &Address,
+) -> Result<bool, SignerError> {
+    check_signature_length(signature)?;
+
+    let payload = signed_bytes_payload(data);
+
+    match (signature.sig_type, signer.protocol()) {
+        (SignatureType::Secp256k1, Protocol::Secp256k1) => {
+            let rs = <[u8; 64]>::try_from(&signature.bytes[..64])?;
+
+            verify_secp256k1_no_recovery(&utils::blake2b_256(&payload), &rs, signer)
+        }
+        (SignatureType::BLS, Protocol::BLS) => {
+            let pk = bls_signatures::PublicKey::from_bytes(&signer.payload_bytes())?;
+            let sig = bls_signatures::Signature::from_bytes(signature.bytes())?;
+
+            Ok(pk.verify(sig, &payload))
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Sign a transaction and return a raw signature (RSV format).
+///
+/// Only secp256k1 and BLS senders can sign. Delegated (f4) senders can't be represented by
+/// the `fvm_shared` `Address` used here, use `transaction_sign_delegated_raw` for f410 senders.
+///
+/// # Arguments
+///
+/// * `message` - an unsigned filecoin message
+/// * `private_key` - a `PrivateKey`
+///
+pub fn transaction_sign_raw(
+    message: &Message,
+    private_key: &PrivateKey,
+) -> Result<Signature, SignerError> {
+    // the `from` address protocol let us know which signing scheme to use
+    let signature = match message.from.protocol() {
+        fvm_shared::address::Protocol::Secp256k1 => {
+            transaction_sign_secp56k1_raw(&message.to_signing_bytes(), private_key)?
+        }
+        fvm_shared::address::Protocol::BLS => {
+            transaction_sign_bls_raw(&message.to_signing_bytes(), private_key)?
+        }
+        _ => {
+            return Err(SignerError::UnknownSigningProtocol);
+        }
+    };
+
+    Ok(signature)
+}
+
+fn eth_chain_id(network: Network) -> u64 {
+    match network {
+        Network::Mainnet => FILECOIN_MAINNET_CHAIN_ID,
+        Network::Testnet => FILECOIN_CALIBRATION_CHAIN_ID,
+    }
+}
+
+fn eth_tx_signing_payload(message: &Message) -> Result<Vec<u8>, SignerError> {
+    let chain_id = eth_chain_id(message.from.network());
+
+    // Only ID addresses have an Ethereum form (masked ID address 0xff00..00<id>)
+    let mut to = vec![0xff];
+    to.extend_from_slice(&[0u8; 11]);
+    to.extend_from_slice(&message.to.id()?.to_be_bytes());
+
+    let input = match message.method_num {
+        fvm_shared::METHOD_SEND if message.params.bytes().is_empty() => vec![],
+        EVM_INVOKE_CONTRACT_METHOD => {
+            let input: serde_bytes::ByteBuf = RawBytes::deserialize(&message.params)?;
+            input.into_vec()
+        }
+        _ => {
+            return Err(SignerError::GenericString(
+                "Only sends and contract invocations can be signed as Ethereum transactions"
+                    .to_string(),
+            ));
+        }
+    };
+
+    if message.gas_limit < 0 {
+        return Err(SignerError::InvalidGasParams(
+            "`gas_limit` can't be negative".to_string(),
+        ));
+    }
+
+    let token_amount_bytes = |amount: &TokenAmount, field: &str| -> Result<Vec<u8>, SignerError> {
+        if amount.is_negative() {
+            return Err(SignerError::NegativeAmount(field.to_string()));
+        }
+        Ok(utils::rlp_encode_uint(&amount.to_bytes_be().1))
+    };
+
+    let tx = utils::rlp_encode_list(&[
+        utils::rlp_encode_uint(&chain_id.to_be_bytes()),
+        utils::rlp_encode_uint(&message.sequence.to_be_bytes()),
+        token_amount_bytes(&message.gas_premium, "gas_premium")?,
+        token_amount_bytes(&message.gas_fee_cap, "gas_fee_cap")?,
+        utils::rlp_encode_uint(&(message.gas_limit as u64).to_be_bytes()),
+        utils::rlp_encode_bytes(&to),
+        token_amount_bytes(&message.value, "value")?,
+        utils::rlp_encode_bytes(&input),
+        // empty access list
+        utils::rlp_encode_list(&[]),
+    ]);
+
+    let mut payload = vec![EIP1559_TX_TYPE];
+    payload.extend(tx);
+
+    Ok(payload)
+}
+
+/// Sign a transaction the Ethereum way (FEVM) and return a raw signature (RSV format).
+///
+/// The message is turned into an EIP-1559 transaction (the chain id is given by the `from`
+/// network) and the keccak256 digest of its RLP encoding is signed. The recovery id `v` is the
+/// EIP-1559 y-parity (0 or 1); use `utils::to_eth_recoverable` to get the legacy 27/28 form.
+///
+/// # Arguments
+///
+/// * `message` - an unsigned filecoin message, sent to an ID address
+/// * `private_key` - a secp256k1 `PrivateKey`
+///
+pub fn transaction_sign_eth_raw(
+    message: &Message,
+    private_key: &PrivateKey,
+) -> Result<Signature, SignerError> {
+    let payload = eth_tx_signing_payload(message)?;
+
+    sign_secp256k1_digest(&utils::keccak256(&payload), private_key)
+}
+
+/// Sign a transaction sent by a delegated f410 address (an Ethereum account) and return a raw
+/// signature (RSV format).
+///
+/// `fvm_shared::address::Address` can't represent delegated addresses yet, so the sender is
+/// given as a string and the `from` of `message` is ignored. The sender must be the Ethereum
+/// address of `private_key`, the message is then signed as with `transaction_sign_eth_raw` on
+/// the network of the sender.
+///
+/// # Arguments
+///
+/// * `message` - an unsigned filecoin message, sent to an ID address
+/// * `sender` - the f410 address sending the message
+/// * `private_key` - the secp256k1 `PrivateKey` of `sender`
+///
+pub fn transaction_sign_delegated_raw(
+    message: &Message,
+    sender: &str,
+    private_key: &PrivateKey,
+) -> Result<Signature, SignerError> {
+    let sender_eth = utils::f4_to_eth(sender)?;
+
+    // Ethereum address: last 20 bytes of the keccak256 of the uncompressed public key
+    let secret_key = libsecp256k1::SecretKey::parse_slice(&private_key.0)?;
+    let public_key = libsecp256k1::PublicKey::from_secret_key(&secret_key);
+    let public_key_hash = utils::keccak256(&public_key.serialize()[1..]);
+
+    if public_key_hash[32 - utils::ETH_ADDRESS_LEN..] != sender_eth {
+        return Err(SignerError::AddressMismatch);
+    }
+
+    let network = if sender.starts_with('t') {
+        Network::Testnet
+    } else {
+        Network::Mainnet
+    };
+
+    let mut message = message.clone();
+    utils::message_set_network(&mut message, network);
+
+    transaction_sign_eth_raw(&message, private_key)
+}
+
+/// Sign a transaction the Ethereum way (FEVM) and return the R, S and V components of the
+/// signature, for tooling that needs them separately.
+///
+/// With `eip155_v` set, `v` is given in the EIP-155 form (chain_id * 2 + 35 + recovery id),
+/// which doesn't fit in the last byte of a RSV signature; otherwise it is the y-parity (0 or 1).
+///
+/// # Arguments
+///
+/// * `message` - an unsigned filecoin message, sent to an ID address
+/// * `private_key` - a secp256k1 `PrivateKey`
+/// * `eip155_v` - `true` to return `v` in the EIP-155 form
+///
+pub fn transaction_sign_eth_rsv(
+    message: &Message,
+    private_key: &PrivateKey,
+    eip155_v: bool,
+) -> Result<([u8; 32], [u8; 32], u64), SignerError> {
+    let signature = transaction_sign_eth_raw(message, private_key)?;
+    let (r, s, recovery) = utils::split_signature_rsv(&signature)?;
+
+    let v = if eip155_v {
+        utils::to_eip155_v(recovery, eth_chain_id(message.from.network()))?
+    } else {
+        recovery as u64
+    };
+
+    Ok((r, s, v))
+}
+
+/// Sign a transaction and return a signed message (message + signature).
+///
+/// # Arguments
+///
+/// * `message` - an unsigned filecoin message
+/// * `private_key` - a `PrivateKey`
+///
+pub fn transaction_sign(
+    message: &Message,
+    private_key: &PrivateKey,
+) -> Result<SignedMessage, SignerError> {
+    let signature = transaction_sign_raw(message, private_key)?;
+
+    let signed_message = SignedMessage {
+        message: message.to_owned(),
+        signature,
+    };
+
+    Ok(signed_message)
+}
+
+/// Maximum size of a serialized message accepted by the message pool
+const MESSAGE_MAX_SIZE: usize = 64 << 10;
+
+/// Limits checked by `transaction_sign_checked` before signing a message
+#[derive(Debug, Clone, Copy)]
+pub struct SignLimits {
+    /// Maximum size of the message params, in bytes
+    pub max_params_bytes: usize,
+    /// Maximum gas limit of the message
+    pub max_gas_limit: i64,
+}
+
+impl Default for SignLimits {
+    /// Protocol constraints: params can't be bigger than the 64 KiB message size limit and a
+    /// message can't use more gas than the block gas limit
+    fn default() -> Self {
+        SignLimits {
+            max_params_bytes: 64 << 10,
+            max_gas_limit: 10_000_000_000,
+        }
+    }
+}
+
+/// Sign a transaction after checking its params size and gas limit and return a signed message.
+/// The serialized message is also checked against the 64 KiB message pool limit, whatever
+/// the limits given.
+///
+/// # Arguments
+///
+/// * `message` - an unsigned filecoin message
+/// * `private_key` - a `PrivateKey`
+/// * `limits` - the limits the message must be within
+///
+pub fn transaction_sign_checked(
+    message: &Message,
+    private_key: &PrivateKey,
+    limits: SignLimits,
+) -> Result<SignedMessage, SignerError> {
+    let params_len = message.params.bytes().len();
+    if params_len > limits.max_params_bytes {
+        return Err(SignerError::MessageLimitExceeded(format!(
+            "params are {} bytes, maximum is {}",
+            params_len, limits.max_params_bytes
+        )));
+    }
+
+    if message.gas_limit > limits.max_gas_limit {
+        return Err(SignerError::MessageLimitExceeded(format!(
+            "gas limit is {}, maximum is {}",
+            message.gas_limit, limits.max_gas_limit
+        )));
+    }
+
+    let message_len = message.marshal_cbor()?.len();
+    if message_len > MESSAGE_MAX_SIZE {
+        return Err(SignerError::MessageLimitExceeded(format!(
+            "message is {} bytes, maximum is {}",
+            message_len, MESSAGE_MAX_SIZE
+        )));
+    }
+
+    transaction_sign(message, private_key)
+}
+
+/// Sign a transaction with a key held outside of the crate (e.g. an HSM or a KMS) and return
+/// the signed message.
+///
+/// The closure receives the bytes to sign: the blake2b digest of the signing bytes for
+/// secp256k1 senders, the signing bytes themselves for BLS senders. It must return the raw
+/// signature (RSV format for secp256k1).
+///
+/// # Arguments
+///
+/// * `message` - an unsigned filecoin message
+/// * `signer` - a closure signing the given bytes with the `from` address key
+///
+pub fn transaction_sign_with<F>(message: &Message, signer: F) -> Result<SignedMessage, SignerError>
+where
+    F: FnOnce(&[u8]) -> Result<Vec<u8>, SignerError>,
+{
+    let signing_bytes = message.to_signing_bytes();
+
+    let signature = match message.from.protocol() {
+        Protocol::Secp256k1 => {
+            Signature::new_secp256k1(signer(&utils::blake2b_256(&signing_bytes))?)
+        }
+        Protocol::BLS => Signature::new_bls(signer(&signing_bytes)?),
+        _ => {
+            return Err(SignerError::UnknownSigningProtocol);
+        }
+    };
+
+    check_signature_length(&signature)?;
+
+    Ok(SignedMessage {
+        message: message.to_owned(),
+        signature,
+    })
+}
+
+/// Sign a transaction and return the signed message serialized as CBOR, ready to be pushed.
+///
+/// # Arguments
+///
+/// * `message` - an unsigned filecoin message
+/// * `private_key` - a `PrivateKey`
+///
+pub fn transaction_sign_to_cbor(
+    message: &Message,
+    private_key: &PrivateKey,
+) -> Result<Vec<u8>, SignerError> {
+    let signed_message = transaction_sign(message, private_key)?;
+
+    Ok(signed_message.marshal_cbor()?)
 }
 
-fn transaction_sign_bls_raw(
+/// Sign a transaction and return the signed message serialized as a CBOR hexstring.
+///
+/// # Arguments
+///
+/// * `message` - an unsigned filecoin message
+/// * `private_key` - a `PrivateKey`
+///
+pub fn transaction_sign_to_cbor_hex(
     message: &Message,
     private_key: &PrivateKey,
-) -> Result<Signature, SignerError> {
-    let sk = bls_signatures::PrivateKey::from_bytes(&private_key.0)?;
-    let sig = sk.sign(message.to_signing_bytes());
-    let signature = Signature::new_bls(sig.as_bytes());
-
-    Ok(signature)
+) -> Result<String, SignerError> {
+    Ok(hex::encode(transaction_sign_to_cbor(message, private_key)?))
 }
 
-/// Sign a transaction and return a raw signature (RSV format).
+/// Sign a transaction after checking that the private key is the one of the `from` address
+/// and return a signed message. Fails with `AddressMismatch` otherwise.
 ///
 /// # Arguments
 ///
 /// * `message` - an unsigned filecoin message
 /// * `private_key` - a `PrivateKey`
 ///
-pub fn transaction_sign_raw(
+pub fn transaction_sign_verified(
     message: &Message,
     private_key: &PrivateKey,
-) -> Result<Signature, SignerError> {
-    // the `from` address protocol let us know which signing scheme to use
-    let signature = match message.from.protocol() {
-        fvm_shared::address::Protocol::Secp256k1 => {
-            transaction_sign_secp56k1_raw(message, private_key)?
-        }
-        fvm_shared::address::Protocol::BLS => transaction_sign_bls_raw(message, private_key)?,
+) -> Result<SignedMessage, SignerError> {
+    let extended_key = match message.from.protocol() {
+        Protocol::Secp256k1 => key_recover(private_key, false)?,
+        Protocol::BLS => key_recover_bls(private_key, false)?,
         _ => {
-            return Err(SignerError::GenericString(
-                "Unknown signing protocol".to_string(),
-            ));
+            return Err(SignerError::UnknownSigningProtocol);
         }
     };
 
-    Ok(signature)
+    // The address derived from the key is on mainnet, compare regardless of the network
+    let key_address = Address::from_str(&extended_key.address)?;
+    if key_address.payload() != message.from.payload() {
+        return Err(SignerError::AddressMismatch);
+    }
+
+    transaction_sign(message, private_key)
 }
 
-/// Sign a transaction and return a signed message (message + signature).
+/// Message as serialized by Lotus JSON-RPC: fields in the order of the Go struct, followed by
+/// the message CID
+#[derive(serde::Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct LotusMessageJson {
+    version: i64,
+    #[serde(with = "extras::json::address")]
+    to: Address,
+    #[serde(with = "extras::json::address")]
+    from: Address,
+    nonce: u64,
+    #[serde(with = "extras::json::tokenamount")]
+    value: TokenAmount,
+    gas_limit: i64,
+    #[serde(with = "extras::json::tokenamount")]
+    gas_fee_cap: TokenAmount,
+    #[serde(with = "extras::json::tokenamount")]
+    gas_premium: TokenAmount,
+    method: MethodNum,
+    #[serde(with = "extras::json::rawbytes")]
+    params: RawBytes,
+    #[serde(rename = "CID", with = "extras::json::cid_link")]
+    cid: Cid,
+}
+
+impl LotusMessageJson {
+    fn new(message: &Message) -> Result<Self, SignerError> {
+        Ok(LotusMessageJson {
+            version: message.version,
+            to: message.to,
+            from: message.from,
+            nonce: message.sequence,
+            value: message.value.clone(),
+            gas_limit: message.gas_limit,
+            gas_fee_cap: message.gas_fee_cap.clone(),
+            gas_premium: message.gas_premium.clone(),
+            method: message.method_num,
+            params: message.params.clone(),
+            cid: message.cid()?,
+        })
+    }
+}
+
+/// Signed message as serialized by Lotus JSON-RPC, the envelope expected by `MpoolPush`
+#[derive(serde::Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct LotusSignedMessageJson {
+    message: LotusMessageJson,
+    #[serde(with = "extras::signature::SignatureAPI")]
+    signature: Signature,
+    #[serde(rename = "CID", with = "extras::json::cid_link")]
+    cid: Cid,
+}
+
+impl LotusSignedMessageJson {
+    fn new(signed_message: &SignedMessage) -> Result<Self, SignerError> {
+        // Like Lotus, BLS signed messages are identified by the CID of the unsigned message
+        let cid = if signed_message.signature.sig_type == SignatureType::BLS {
+            signed_message.message.cid()?
+        } else {
+            signed_message.cid()?
+        };
+
+        Ok(LotusSignedMessageJson {
+            message: LotusMessageJson::new(&signed_message.message)?,
+            signature: signed_message.signature.clone(),
+            cid,
+        })
+    }
+}
+
+/// Serialize a signed message to the JSON expected by the Lotus `MpoolPush` method:
+/// `{"Message": {...}, "Signature": {"Type": <int>, "Data": <base64>}, "CID": {"/": <cid>}}`.
 ///
 /// # Arguments
 ///
-/// * `message` - an unsigned filecoin message
+/// * `sm` - a signed message
+///
+pub fn signed_message_to_json(sm: &SignedMessage) -> Result<String, SignerError> {
+    serde_json::to_string(&LotusSignedMessageJson::new(sm)?)
+        .map_err(|err| SignerError::GenericString(err.to_string()))
+}
+
+/// Sign a hex encoded unsigned transaction with a hex encoded private key and return the
+/// signed transaction CBOR as hex. Meant for bindings where byte arrays are awkward to pass.
+///
+/// # Arguments
+///
+/// * `cbor_hex` - the hex encoded CBOR of an unsigned filecoin message
+/// * `private_key_hex` - the hex encoded private key
+/// * `testnet` - boolean value `true` if testnet or `false` for mainnet
+///
+#[cfg(feature = "with-hex-api")]
+pub fn transaction_sign_hex(
+    cbor_hex: &str,
+    private_key_hex: &str,
+    testnet: bool,
+) -> Result<String, SignerError> {
+    let message = transaction_parse_unsigned(&hex::decode(cbor_hex)?, testnet)?;
+    let private_key = PrivateKey::try_from(hex::decode(private_key_hex)?)?;
+
+    let signed_message = transaction_sign(&message, &private_key)?;
+
+    Ok(hex::encode(signed_message.marshal_cbor()?))
+}
+
+/// Sign many transactions in parallel with the same key and return the signed messages,
+/// in the same order as the input. The first error encountered is returned.
+///
+/// # Arguments
+///
+/// * `messages` - the unsigned filecoin messages
 /// * `private_key` - a `PrivateKey`
 ///
-pub fn transaction_sign(
-    message: &Message,
+pub fn transaction_sign_batch(
+    messages: &[Message],
     private_key: &PrivateKey,
-) -> Result<SignedMessage, SignerError> {
-    let signature = transaction_sign_raw(message, private_key)?;
+) -> Result<Vec<SignedMessage>, SignerError> {
+    messages
+        .par_iter()
+        .map(|message| transaction_sign(message, private_key))
+        .collect()
+}
 
-    let signed_message = SignedMessage {
-        message: message.to_owned(),
-        signature,
-    };
+/// Reject secp256k1 signatures whose S value is in the upper half of the curve order: (R, S)
+/// and (R, n - S) both verify, low-S is the canonical form
+fn check_low_s(signature: &Signature) -> Result<(), SignerError> {
+    check_signature_length(signature)?;
 
-    Ok(signed_message)
+    let signature_rs = libsecp256k1::Signature::parse_standard_slice(&signature.bytes[..64])?;
+
+    if signature_rs.s.is_high() {
+        return Err(SignerError::MalleableSignature);
+    }
+
+    Ok(())
+}
+
+fn verify_secp256k1_signature(
+    signature: &Signature,
+    cbor: &Vec<u8>,
+    strict: bool,
+) -> Result<bool, SignerError> {
+    if strict {
+        check_low_s(signature)?;
+    }
+
+    // CBOR addresses don't carry a network: the recovered address is compared using the
+    // network of the parsed sender, so the network used to parse doesn't matter
+    verify_secp256k1_signature_with_network(signature, cbor, Network::Mainnet)
 }
 
-fn verify_secp256k1_signature(signature: &Signature, cbor: &Vec<u8>) -> Result<bool, SignerError> {
-    let network = Network::Testnet;
+fn verify_secp256k1_signature_with_network(
+    signature: &Signature,
+    cbor: &[u8],
+    network: Network,
+) -> Result<bool, SignerError> {
+    let message = transaction_parse(cbor, network == Network::Testnet)?.get_message();
+
+    verify_secp256k1_message(signature, &message)
+}
 
+/// Recover the public key of a secp256k1 signature. The parsed signature and the digest are
+/// returned as well so callers can verify without parsing and hashing again.
+fn recover_secp256k1_public_key(
+    signature: &Signature,
+    message: &Message,
+) -> Result<
+    (
+        SECP256K1PublicKey,
+        libsecp256k1::Signature,
+        libsecp256k1::Message,
+    ),
+    SignerError,
+> {
     let signature_rs = libsecp256k1::Signature::parse_standard_slice(&signature.bytes[..64])?;
     let recovery_id = libsecp256k1::RecoveryId::parse(signature.bytes[64])?;
 
-    // Should be default network here
-    // FIXME: For now only testnet
-    let tx = transaction_parse(cbor, network == Network::Testnet)?;
-
-    // Decode the CBOR transaction hex string into CBOR transaction buffer
-    let message_digest = utils::get_digest(cbor.as_ref())?;
+    let message_digest = utils::blake2b_256(&message.to_signing_bytes());
 
     let blob_to_sign = libsecp256k1::Message::parse_slice(&message_digest)?;
 
     let public_key = libsecp256k1::recover(&blob_to_sign, &signature_rs, &recovery_id)?;
-    let mut from = Address::new_secp256k1(public_key.serialize().as_ref())?;
-    from.set_network(network);
 
-    let tx_from = match tx {
-        MessageTxAPI::Message(tx) => tx.from,
-        MessageTxAPI::SignedMessage(tx) => tx.message.from,
-    };
-    let expected_from = from.to_string();
+    Ok((public_key, signature_rs, blob_to_sign))
+}
+
+/// Recover the public key that produced a secp256k1 transaction signature.
+///
+/// The signature is not checked against the transaction sender, use `verify_signature`
+/// for that.
+///
+/// # Arguments
+///
+/// * `signature` - RSV format signature
+/// * `cbor` - the CBOR transaction the signature covers
+///
+pub fn recover_pubkey_from_signature(
+    signature: &Signature,
+    cbor: &[u8],
+) -> Result<PublicKey, SignerError> {
+    if signature.sig_type != SignatureType::Secp256k1 {
+        return Err(SignerError::UnknownSigningProtocol);
+    }
+    check_signature_length(signature)?;
+
+    let message = transaction_parse(cbor, false)?.get_message();
+    let (public_key, _, _) = recover_secp256k1_public_key(signature, &message)?;
+
+    Ok(PublicKey::SECP256K1PublicKey(public_key))
+}
+
+fn verify_secp256k1_message(signature: &Signature, message: &Message) -> Result<bool, SignerError> {
+    let (public_key, signature_rs, blob_to_sign) =
+        recover_secp256k1_public_key(signature, message)?;
+
+    // The recovered address uses the same network as the message sender
+    let mut from = Address::new_secp256k1(public_key.serialize().as_ref())?;
+    from.set_network(message.from.network());
 
     // Compare recovered public key with the public key from the transaction
-    if tx_from.to_string() != expected_from {
+    if message.from.to_string() != from.to_string() {
         return Ok(false);
     }
 
@@ -416,11 +1952,60 @@ fn verify_secp256k1_signature(signature: &Signature, cbor: &Vec<u8>) -> Result<b
     ))
 }
 
+/// Verify a secp256k1 signature without knowing the network of the signer. The CBOR message
+/// doesn't record a network, so the recovered signer address is rendered for mainnet and for
+/// testnet and compared with `signer_address`. Return the matching network, or `None` if the
+/// signature is invalid or doesn't match `signer_address` on either network.
+///
+/// # Arguments
+///
+/// * `signature` - RSV format signature
+/// * `cbor` - The cbor message the signature is supposed to cover
+/// * `signer_address` - The address the signer is known by, `f` or `t` prefixed
+///
+pub fn verify_signature_any_network(
+    signature: &Signature,
+    cbor: &[u8],
+    signer_address: &str,
+) -> Result<Option<Network>, SignerError> {
+    if signature.sig_type != SignatureType::Secp256k1 {
+        return Err(SignerError::UnknownSigningProtocol);
+    }
+    check_signature_length(signature)?;
+
+    let message = transaction_parse(cbor, false)?.get_message();
+
+    let (public_key, signature_rs, blob_to_sign) =
+        recover_secp256k1_public_key(signature, &message)?;
+
+    if !libsecp256k1::verify(&blob_to_sign, &signature_rs, &public_key) {
+        return Ok(None);
+    }
+
+    // The recovered key must also be the sender of the message
+    let mut recovered = Address::new_secp256k1(public_key.serialize().as_ref())?;
+    if recovered.payload() != message.from.payload() {
+        return Ok(None);
+    }
+
+    for network in [Network::Mainnet, Network::Testnet] {
+        recovered.set_network(network);
+        if recovered.to_string() == signer_address {
+            return Ok(Some(network));
+        }
+    }
+
+    Ok(None)
+}
+
 fn verify_bls_signature(signature: &Signature, cbor: &Vec<u8>) -> Result<bool, SignerError> {
     // TODO: need a function to extract from public key from cbor buffer directly
-    let message = transaction_parse(cbor, true)?;
-    let message = message.get_message();
+    let message = transaction_parse(cbor, true)?.get_message();
 
+    verify_bls_message(signature, &message)
+}
+
+fn verify_bls_message(signature: &Signature, message: &Message) -> Result<bool, SignerError> {
     let pk = bls_signatures::PublicKey::from_bytes(&message.from.payload_bytes())?;
 
     let sig = bls_signatures::Signature::from_bytes(signature.bytes())?;
@@ -440,21 +2025,104 @@ fn verify_bls_signature(signature: &Signature, cbor: &Vec<u8>) -> Result<bool, S
 /// * `cbor_buffer` - the CBOR transaction to verify the signature against
 ///
 pub fn verify_signature(signature: &Signature, cbor: &Vec<u8>) -> Result<bool, SignerError> {
+    verify_signature_with_mode(signature, cbor, false)
+}
+
+/// Verify a signature, rejecting malleable (high-S) secp256k1 signatures. Return a boolean.
+///
+/// # Arguments
+///
+/// * `signature` - RSV format signature or BLS signature
+/// * `cbor_buffer` - the CBOR transaction to verify the signature against
+///
+pub fn verify_signature_strict(signature: &Signature, cbor: &Vec<u8>) -> Result<bool, SignerError> {
+    verify_signature_with_mode(signature, cbor, true)
+}
+
+fn verify_signature_with_mode(
+    signature: &Signature,
+    cbor: &Vec<u8>,
+    strict: bool,
+) -> Result<bool, SignerError> {
+    check_signature_length(signature)?;
+
     // TODO: pass signature.bytes instead of the full signature
     let result = match signature.sig_type {
-        SignatureType::Secp256k1 => verify_secp256k1_signature(signature, cbor)?,
+        SignatureType::Secp256k1 => verify_secp256k1_signature(signature, cbor, strict)?,
         SignatureType::BLS => verify_bls_signature(signature, cbor)?,
     };
 
     Ok(result)
 }
 
+/// Verify a signature against an already parsed message, without a CBOR round-trip. Return a boolean.
+///
+/// # Arguments
+///
+/// * `signature` - RSV format signature or BLS signature
+/// * `message` - the message to verify the signature against
+/// * `network` - the network the message sender is expected to be on
+///
+pub fn verify_signature_for_message(
+    signature: &Signature,
+    message: &Message,
+    network: Network,
+) -> Result<bool, SignerError> {
+    check_signature_length(signature)?;
+
+    if message.from.network() != network {
+        return Ok(false);
+    }
+
+    match signature.sig_type {
+        SignatureType::Secp256k1 => verify_secp256k1_message(signature, message),
+        SignatureType::BLS => verify_bls_message(signature, message),
+    }
+}
+
+/// Verify a secp256k1 signature given without its recovery byte. Return a boolean.
+///
+/// Both recovery ids are tried and the signature is valid if either one recovers
+/// to the expected signer.
+///
+/// # Arguments
+///
+/// * `digest` - the 32 bytes digest that was signed
+/// * `rs` - the 64 bytes signature (R || S) without the recovery byte
+/// * `expected_signer` - the address expected to have produced the signature
+///
+pub fn verify_secp256k1_no_recovery(
+    digest: &[u8; 32],
+    rs: &[u8; 64],
+    expected_signer: &Address,
+) -> Result<bool, SignerError> {
+    let signature_rs = libsecp256k1::Signature::parse_standard_slice(rs)?;
+    let message = libsecp256k1::Message::parse(digest);
+
+    for id in 0..2 {
+        let recovery_id = libsecp256k1::RecoveryId::parse(id)?;
+
+        let public_key = match libsecp256k1::recover(&message, &signature_rs, &recovery_id) {
+            Ok(public_key) => public_key,
+            Err(_) => continue,
+        };
+
+        let mut signer = Address::new_secp256k1(public_key.serialize().as_ref())?;
+        signer.set_network(expected_signer.network());
+
+        if &signer == expected_signer {
+            return Ok(libsecp256k1::verify(&message, &signature_rs, &public_key));
+        }
+    }
+
+    Ok(false)
+}
+
 fn extract_from_pub_key_from_message(
     cbor_message: &Vec<u8>,
 ) -> Result<bls_signatures::PublicKey, SignerError> {
     let message = transaction_parse(cbor_message, true)?;
-    let unsigned_message_api = message.get_message();
-    let pk = bls_signatures::PublicKey::from_bytes(&unsigned_message_api.from.payload_bytes())?;
+    let pk = bls_signatures::PublicKey::from_bytes(&message.from().payload_bytes())?;
 
     Ok(pk)
 }
@@ -466,6 +2134,16 @@ fn extract_bls_signing_bytes_from_message(cbor_message: &Vec<u8>) -> Result<Vec<
     Ok(unsigned_message_api.to_signing_bytes())
 }
 
+/// Verify a BLS aggregated signature over many messages. Return a boolean.
+///
+/// Each message is checked against the key of its own sender, so the order of the messages
+/// doesn't matter; any permutation of the list verifies.
+///
+/// # Arguments
+///
+/// * `signature` - The BLS aggregated signature
+/// * `cbor_messages` - The CBOR messages the signature is supposed to cover
+///
 pub fn verify_aggregated_signature(
     signature: &Signature,
     cbor_messages: &[Vec<u8>],
@@ -510,16 +2188,194 @@ pub fn verify_aggregated_signature(
     Ok(bls_signatures::verify(&sig, &hashes, pks.as_slice()))
 }
 
-/// Utilitary function to serialize parameters of a message. Return a CBOR hexstring.
+/// Verify a BLS aggregated signature over many messages with the public keys of their senders,
+/// skipping the public key extraction from the messages. Return a boolean.
+///
+/// # Arguments
+///
+/// * `signature` - the aggregated BLS signature
+/// * `messages` - the CBOR messages covered by the signature
+/// * `pubkeys` - the BLS public keys of the senders, in the same order as the messages
+///
+pub fn verify_aggregated_signature_with_keys(
+    signature: &Signature,
+    messages: &[Vec<u8>],
+    pubkeys: &[BLSPublicKey],
+) -> Result<bool, SignerError> {
+    if messages.len() != pubkeys.len() {
+        return Err(SignerError::LengthMismatch {
+            messages: messages.len(),
+            keys: pubkeys.len(),
+        });
+    }
+
+    let sig = bls_signatures::Signature::from_bytes(signature.bytes())?;
+
+    let signing_bytes = messages
+        .iter()
+        .map(extract_bls_signing_bytes_from_message)
+        .collect::<Result<Vec<_>, SignerError>>()?;
+
+    let hashes = signing_bytes
+        .par_iter()
+        .map(|signing_bytes| bls_signatures::hash(signing_bytes.as_ref()))
+        .collect::<Vec<_>>();
+
+    Ok(bls_signatures::verify(&sig, &hashes, pubkeys))
+}
+
+/// Verify many independent signatures in parallel. Return one boolean per item, in order.
+///
+/// Items are not short-circuited: a signature that fails to verify, or that cannot
+/// be checked at all (e.g. a malformed message), is reported as `false` without
+/// affecting the rest of the batch.
+///
+/// # Arguments
+///
+/// * `items` - pairs of signature and the CBOR transaction it signs
+///
+pub fn verify_signatures_batch(items: &[(Signature, Vec<u8>)]) -> Result<Vec<bool>, SignerError> {
+    let results = items
+        .par_iter()
+        .map(|(signature, cbor)| verify_signature(signature, cbor).unwrap_or(false))
+        .collect();
+
+    Ok(results)
+}
+
+/// Sign many messages with BLS keys and aggregate the signatures into one. Return the aggregated signature.
+///
+/// # Arguments
+///
+/// * `messages` - The unsigned messages, all sent by BLS addresses
+/// * `private_keys` - The BLS private keys, in the same order as the messages
+///
+pub fn transaction_sign_bls_aggregate(
+    messages: &[Message],
+    private_keys: &[PrivateKey],
+) -> Result<Signature, SignerError> {
+    if messages.len() != private_keys.len() {
+        return Err(SignerError::LengthMismatch {
+            messages: messages.len(),
+            keys: private_keys.len(),
+        });
+    }
+
+    if messages
+        .iter()
+        .any(|message| message.from.protocol() != Protocol::BLS)
+    {
+        return Err(SignerError::UnknownSigningProtocol);
+    }
+
+    let sigs = messages
+        .par_iter()
+        .zip(private_keys.par_iter())
+        .map(|(message, private_key)| {
+            let sk = bls_signatures::PrivateKey::from_bytes(&private_key.0)?;
+            Ok(sk.sign(message.to_signing_bytes()))
+        })
+        .collect::<Result<Vec<_>, SignerError>>()?;
+
+    let aggregated_signature = bls_signatures::aggregate(&sigs)?;
+
+    Ok(Signature::new_bls(aggregated_signature.as_bytes()))
+}
+
+/// Incremental verifier for a BLS aggregated signature, fed with CBOR messages as they arrive.
+///
+/// Messages can be pushed in any order, only the pairing of each message with its sender matters.
+#[derive(Default)]
+pub struct AggregateVerifier {
+    public_keys: Vec<BLSPublicKey>,
+    hashes: Vec<G2Projective>,
+}
+
+impl AggregateVerifier {
+    pub fn new() -> Self {
+        AggregateVerifier {
+            public_keys: Vec::new(),
+            hashes: Vec::new(),
+        }
+    }
+
+    /// Add a message covered by the aggregated signature. The message is hashed to the curve
+    /// right away, so `finish` only has the pairing left to do.
+    ///
+    /// # Arguments
+    ///
+    /// * `cbor_message` - A CBOR unsigned message sent by a BLS address
+    ///
+    pub fn push(&mut self, cbor_message: &[u8]) -> Result<(), SignerError> {
+        let message = transaction_parse(cbor_message, true)?.get_message();
+        let pk = BLSPublicKey::from_bytes(&message.from.payload_bytes())?;
+
+        self.public_keys.push(pk);
+        self.hashes
+            .push(bls_signatures::hash(&message.to_signing_bytes()));
+
+        Ok(())
+    }
+
+    /// Verify the aggregated signature against all the messages pushed so far. Return a boolean.
+    ///
+    /// # Arguments
+    ///
+    /// * `aggregate_sig` - The BLS aggregated signature
+    ///
+    pub fn finish(self, aggregate_sig: &Signature) -> Result<bool, SignerError> {
+        let sig = bls_signatures::Signature::from_bytes(aggregate_sig.bytes())?;
+
+        Ok(bls_signatures::verify(
+            &sig,
+            &self.hashes,
+            self.public_keys.as_slice(),
+        ))
+    }
+}
+
+/// Utilitary function to serialize parameters of a message. Return a CBOR hexstring.
+///
+/// # Arguments
+///
+/// * `params` - Parameters to serialize
+
+pub fn serialize_params(params: MessageParams) -> Result<Vec<u8>, SignerError> {
+    let serialized_params = params.serialize()?;
+    let message_cbor = serialized_params.bytes().to_vec();
+    Ok(message_cbor)
+}
+
+/// Utilitary function to serialize parameters for actors that are not modeled by `MessageParams`.
+/// Return the CBOR encoded parameters.
+///
+/// # Arguments
+///
+/// * `value` - A generic CBOR value holding the parameters
+///
+pub fn serialize_raw_params(value: &serde_cbor::Value) -> Result<Vec<u8>, SignerError> {
+    let params_cbor = serde_cbor::to_vec(value)?;
+    Ok(params_cbor)
+}
+
+/// Compute the robust (f2) address of the payment channel created by the init actor `Exec` call
+/// of a message, the same way the VM derives new actor addresses.
 ///
 /// # Arguments
 ///
-/// * `params` - Parameters to serialize
+/// * `creator` - The key address (f1/f3) sending the message;
+/// * `nonce` - The nonce of the message creating the payment channel;
+///
+pub fn compute_paych_address(creator: &Address, nonce: u64) -> Result<Address, SignerError> {
+    let mut data = to_vec(creator)?;
+    data.extend_from_slice(&nonce.to_be_bytes());
+    // Number of actors already created by the message: the channel is the first one
+    data.extend_from_slice(&0u64.to_be_bytes());
 
-pub fn serialize_params(params: MessageParams) -> Result<Vec<u8>, SignerError> {
-    let serialized_params = params.serialize()?;
-    let message_cbor = serialized_params.bytes().to_vec();
-    Ok(message_cbor)
+    let mut address = Address::new_actor(&data);
+    address.set_network(creator.network());
+
+    Ok(address)
 }
 
 /// Sign a voucher for payment channel
@@ -559,6 +2415,34 @@ pub fn sign_voucher(
     Ok(cbor_voucher)
 }
 
+/// Sign a voucher for payment channel with a BLS private key
+///
+/// # Arguments
+///
+/// * `voucher_string` - Voucher as base64 string;
+/// * `private_key` - BLS private key;
+///
+pub fn sign_voucher_bls(
+    voucher_string: String,
+    private_key: &PrivateKey,
+) -> Result<String, SignerError> {
+    let decoded_voucher = base64::decode(voucher_string)?;
+    let mut voucher: paych::SignedVoucher = from_slice(&decoded_voucher)?;
+
+    let svb = voucher
+        .signing_bytes()
+        .map_err(|err| SignerError::GenericString(err.to_string()))?;
+    // Same digest as the one `verify_voucher_signature` checks the BLS signature against
+    let digest = utils::get_digest_voucher(&svb)?;
+
+    voucher.signature = Some(transaction_sign_bls_raw(&digest, private_key)?);
+
+    let binary_voucher = to_vec(&voucher)?;
+    let cbor_voucher = base64::encode(binary_voucher);
+
+    Ok(cbor_voucher)
+}
+
 /// Create a voucher for payment channel
 ///
 /// # Arguments
@@ -578,6 +2462,44 @@ pub fn create_voucher(
     lane: u64,
     nonce: u64,
     min_settle_height: i64,
+) -> Result<String, SignerError> {
+    create_voucher_full(
+        payment_channel_address,
+        time_lock_min,
+        time_lock_max,
+        amount,
+        lane,
+        nonce,
+        min_settle_height,
+        Vec::new(),
+        None,
+    )
+}
+
+/// Create a voucher for payment channel, with a hashlock secret and an external actor verification
+///
+/// # Arguments
+///
+/// * `payment_channel_address` - The payment channel address;
+/// * `time_lock_min` - Time lock min;
+/// * `time_lock_maax` - Time lock max;
+/// * `amount` - Amount in the voucher;
+/// * `lane` - Lane of the voucher;
+/// * `nonce` - Next nonce of the voucher;
+/// * `secret_pre_image` - Preimage of the secret that must be revealed to redeem the voucher;
+/// * `extra` - Actor method to call to validate the voucher;
+///
+#[allow(clippy::too_many_arguments)]
+pub fn create_voucher_full(
+    payment_channel_address: String,
+    time_lock_min: i64,
+    time_lock_max: i64,
+    amount: String,
+    lane: u64,
+    nonce: u64,
+    min_settle_height: i64,
+    secret_pre_image: Vec<u8>,
+    extra: Option<paych::ModVerifyParams>,
 ) -> Result<String, SignerError> {
     let pch = fvm_shared::address::Address::from_str(&payment_channel_address)?;
     let amount = match fvm_shared::bigint::BigInt::parse_bytes(amount.as_bytes(), 10) {
@@ -593,8 +2515,8 @@ pub fn create_voucher(
         channel_addr: pch,
         time_lock_min,
         time_lock_max,
-        secret_pre_image: Vec::new(),
-        extra: None,
+        secret_pre_image,
+        extra,
         lane,
         nonce,
         amount,
@@ -613,102 +2535,278 @@ pub fn create_voucher(
 /// # Arguments
 ///
 /// * `params_b64_string` - The base64 params string;
-/// * `actor_type` - The string that tell the actor type;
+/// * `actor_type` - The actor type the params are destined to;
 /// * `method` - Method for which we want to deserialize the params;
 pub fn deserialize_params(
     params_b64_string: String,
-    actor_type: String,
+    actor_type: ActorType,
     method: u64,
 ) -> Result<MessageParams, SignerError> {
     let params_decode = base64::decode(params_b64_string)?;
     let serialized_params = RawBytes::new(params_decode);
 
-    // Deserialize pre-FVM init actor
-    if actor_type.as_str() == "init" {
-        match FromPrimitive::from_u64(method) {
+    match actor_type {
+        // Deserialize pre-FVM init actor
+        ActorType::Init => match FromPrimitive::from_u64(method) {
             Some(MethodInit::Exec) => {
                 let params: ExecParams = RawBytes::deserialize(&serialized_params)?;
-                return Ok(MessageParams::ExecParams(params));
-            }
-            _ => {
-                return Err(SignerError::GenericString(
-                    "Unknown method for init actor.".to_string(),
-                ));
-            }
-        }
-    }
 
-    // Deserialize pre-FVM multisig actor
-    if actor_type.as_str() == "multisig" {
-        match FromPrimitive::from_u64(method) {
+                Ok(MessageParams::ExecParams(params))
+            }
+            _ => Err(SignerError::GenericString(
+                "Unknown method for init actor.".to_string(),
+            )),
+        },
+        // Deserialize pre-FVM multisig actor
+        ActorType::Multisig => match FromPrimitive::from_u64(method) {
             Some(multisig::Method::Propose) => {
                 let params = serialized_params.deserialize::<multisig::ProposeParams>()?;
 
-                return Ok(MessageParams::ProposeParams(params));
+                Ok(MessageParams::ProposeParams(params))
             }
             Some(multisig::Method::Approve) | Some(multisig::Method::Cancel) => {
                 let params = serialized_params.deserialize::<multisig::TxnIDParams>()?;
 
-                return Ok(MessageParams::TxnIDParams(params));
+                Ok(MessageParams::TxnIDParams(params))
             }
             Some(multisig::Method::AddSigner) => {
                 let params = serialized_params.deserialize::<multisig::AddSignerParams>()?;
 
-                return Ok(MessageParams::AddSignerParams(params));
+                Ok(MessageParams::AddSignerParams(params))
             }
             Some(multisig::Method::RemoveSigner) => {
                 let params = serialized_params.deserialize::<multisig::RemoveSignerParams>()?;
 
-                return Ok(MessageParams::RemoveSignerParams(params));
+                Ok(MessageParams::RemoveSignerParams(params))
             }
             Some(multisig::Method::SwapSigner) => {
                 let params = serialized_params.deserialize::<multisig::SwapSignerParams>()?;
 
-                return Ok(MessageParams::SwapSignerParams(params));
+                Ok(MessageParams::SwapSignerParams(params))
             }
             Some(multisig::Method::ChangeNumApprovalsThreshold) => {
                 let params = serialized_params
                     .deserialize::<multisig::ChangeNumApprovalsThresholdParams>()?;
 
-                return Ok(MessageParams::ChangeNumApprovalsThresholdParams(params));
+                Ok(MessageParams::ChangeNumApprovalsThresholdParams(params))
             }
             Some(multisig::Method::LockBalance) => {
                 let params = serialized_params.deserialize::<multisig::LockBalanceParams>()?;
 
-                return Ok(MessageParams::LockBalanceParams(params));
+                Ok(MessageParams::LockBalanceParams(params))
             }
-            _ => {
-                return Err(SignerError::GenericString(
-                    "Unknown method for multisig actor.".to_string(),
-                ));
-            }
-        }
-    }
-
-    // Deserialize pre-FVM paymentchannel actor
-    if actor_type.as_str() == "paymentchannel" {
-        match FromPrimitive::from_u64(method) {
+            _ => Err(SignerError::GenericString(
+                "Unknown method for multisig actor.".to_string(),
+            )),
+        },
+        // Deserialize pre-FVM paymentchannel actor
+        ActorType::PaymentChannel => match FromPrimitive::from_u64(method) {
             Some(paych::Method::UpdateChannelState) => {
                 let params: fil_actor_paych::UpdateChannelStateParams =
                     RawBytes::deserialize(&serialized_params)?;
 
-                return Ok(MessageParams::UpdateChannelStateParams(params));
+                Ok(MessageParams::UpdateChannelStateParams(params))
             }
             Some(paych::Method::Settle) | Some(paych::Method::Collect) => {
                 /* Note : those method doesn't have params to decode */
-                return Ok(MessageParams::MessageParamsSerialized("".to_string()));
+                Ok(MessageParams::MessageParamsSerialized("".to_string()))
+            }
+            _ => Err(SignerError::GenericString(
+                "Unknown method for paymentchannel actor.".to_string(),
+            )),
+        },
+        // Deserialize verified registry actor
+        ActorType::Verifreg => match FromPrimitive::from_u64(method) {
+            Some(verifreg::Method::AddVerifiedClient) => {
+                let params =
+                    serialized_params.deserialize::<verifreg::AddVerifierClientParams>()?;
+
+                Ok(MessageParams::AddVerifiedClientParams(params))
             }
-            _ => {
-                return Err(SignerError::GenericString(
-                    "Unknown method for paymentchannel actor.".to_string(),
-                ));
+            _ => Err(SignerError::GenericString(
+                "Unknown method for verifreg actor.".to_string(),
+            )),
+        },
+        // Deserialize datacap actor
+        ActorType::Datacap => match method {
+            datacap::method::TRANSFER => {
+                let params = serialized_params.deserialize::<datacap::TransferParams>()?;
+
+                Ok(MessageParams::DatacapTransferParams(params))
+            }
+            _ => Err(SignerError::GenericString(
+                "Unknown method for datacap actor.".to_string(),
+            )),
+        },
+        // Deserialize EVM contract invocation, the calldata is a CBOR byte string
+        ActorType::Evm => match method {
+            EVM_INVOKE_CONTRACT_METHOD => {
+                let calldata: serde_bytes::ByteBuf = serialized_params.deserialize()?;
+
+                Ok(MessageParams::InvokeEVM(calldata.into_vec()))
+            }
+            _ => Err(SignerError::GenericString(
+                "Unknown method for evm actor.".to_string(),
+            )),
+        },
+    }
+}
+
+/// Deserialize Params, with the actor type given as a string (used by the ffi and wasm bindings)
+///
+/// # Arguments
+///
+/// * `params_b64_string` - The base64 params string;
+/// * `actor_type` - The string that tell the actor type (e.g "multisig");
+/// * `method` - Method for which we want to deserialize the params;
+pub fn deserialize_params_str(
+    params_b64_string: String,
+    actor_type: String,
+    method: u64,
+) -> Result<MessageParams, SignerError> {
+    let actor_type = ActorType::from_str(&actor_type)?;
+
+    deserialize_params(params_b64_string, actor_type, method)
+}
+
+/// Deserialize the return value of a multisig Propose (the receipt `return` bytes), which holds
+/// the id of the pending transaction to use in a subsequent Approve or Cancel.
+///
+/// # Arguments
+///
+/// * `b64` - The base64 return value;
+pub fn deserialize_propose_return(b64: String) -> Result<multisig::ProposeReturn, SignerError> {
+    let return_bytes = base64::decode(b64)?;
+
+    Ok(from_slice(&return_bytes)?)
+}
+
+/// Deserialize Params of any actor and method into a generic JSON value, meant for display.
+/// Byte strings (addresses, token amounts, nested params...) are rendered as hex strings,
+/// integers are preserved and CBOR tags (e.g. CIDs) are dropped in favour of their content.
+///
+/// # Arguments
+///
+/// * `params_b64` - The base64 params string;
+pub fn deserialize_params_to_json(params_b64: String) -> Result<serde_json::Value, SignerError> {
+    let params_bytes = base64::decode(params_b64)?;
+    let value: serde_cbor::Value = serde_cbor::from_slice(&params_bytes)?;
+
+    Ok(cbor_value_to_json(value))
+}
+
+fn cbor_value_to_json(value: serde_cbor::Value) -> serde_json::Value {
+    match value {
+        serde_cbor::Value::Null => serde_json::Value::Null,
+        serde_cbor::Value::Bool(b) => serde_json::Value::Bool(b),
+        serde_cbor::Value::Integer(i) => {
+            if let Ok(n) = u64::try_from(i) {
+                serde_json::Value::from(n)
+            } else if let Ok(n) = i64::try_from(i) {
+                serde_json::Value::from(n)
+            } else {
+                // Out of the JSON number range, keep the exact value as a string
+                serde_json::Value::String(i.to_string())
             }
         }
+        serde_cbor::Value::Float(f) => serde_json::Value::from(f),
+        serde_cbor::Value::Bytes(bytes) => serde_json::Value::String(hex::encode(bytes)),
+        serde_cbor::Value::Text(text) => serde_json::Value::String(text),
+        serde_cbor::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(cbor_value_to_json).collect())
+        }
+        serde_cbor::Value::Map(entries) => serde_json::Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match cbor_value_to_json(key) {
+                        serde_json::Value::String(key) => key,
+                        key => key.to_string(),
+                    };
+                    (key, cbor_value_to_json(value))
+                })
+                .collect(),
+        ),
+        serde_cbor::Value::Tag(_, value) => cbor_value_to_json(*value),
+        _ => serde_json::Value::Null,
     }
+}
 
-    Err(SignerError::GenericString(
-        "Actor type not supported.".to_string(),
-    ))
+/// Return the method number/name pairs supported by `deserialize_params` for an actor type
+///
+/// # Arguments
+///
+/// * `actor_type` - The actor type ("init", "multisig", "paymentchannel", "verifreg", "datacap" or "evm");
+pub fn actor_methods(actor_type: &str) -> Option<Vec<(u64, &'static str)>> {
+    match actor_type {
+        "init" => Some(vec![(MethodInit::Exec as u64, "Exec")]),
+        "multisig" => Some(vec![
+            (multisig::Method::Propose as u64, "Propose"),
+            (multisig::Method::Approve as u64, "Approve"),
+            (multisig::Method::Cancel as u64, "Cancel"),
+            (multisig::Method::AddSigner as u64, "AddSigner"),
+            (multisig::Method::RemoveSigner as u64, "RemoveSigner"),
+            (multisig::Method::SwapSigner as u64, "SwapSigner"),
+            (
+                multisig::Method::ChangeNumApprovalsThreshold as u64,
+                "ChangeNumApprovalsThreshold",
+            ),
+            (multisig::Method::LockBalance as u64, "LockBalance"),
+        ]),
+        "paymentchannel" => Some(vec![
+            (
+                paych::Method::UpdateChannelState as u64,
+                "UpdateChannelState",
+            ),
+            (paych::Method::Settle as u64, "Settle"),
+            (paych::Method::Collect as u64, "Collect"),
+        ]),
+        "verifreg" => Some(vec![(
+            verifreg::Method::AddVerifiedClient as u64,
+            "AddVerifiedClient",
+        )]),
+        "datacap" => Some(vec![(datacap::method::TRANSFER, "Transfer")]),
+        "evm" => Some(vec![(EVM_INVOKE_CONTRACT_METHOD, "InvokeContract")]),
+        _ => None,
+    }
+}
+
+/// Multihash code of the identity hash, used by the legacy (pre-FVM) builtin actors code CIDs
+const IDENTITY_HASH_CODE: u64 = 0x00;
+/// Multicodec code of raw binary data, codec of the legacy builtin actors code CIDs
+const RAW_CODEC: u64 = 0x55;
+/// Last actors version using legacy (identity hash) code CIDs
+const LAST_LEGACY_ACTORS_VERSION: u32 = 7;
+
+/// Return the code CID of a builtin actor (e.g "multisig" or "paymentchannel") for an actors
+/// version. Only the versions up to 7, whose code CIDs embed the actor name, are supported:
+/// later versions come with network specific bundles.
+///
+/// # Arguments
+///
+/// * `actors_version` - The actors version (e.g 7)
+/// * `actor_name` - The builtin actor name (e.g "multisig")
+pub fn builtin_actor_code_cid(actors_version: u32, actor_name: &str) -> Result<Cid, SignerError> {
+    let code_name = format!("fil/{}/{}", actors_version, actor_name);
+
+    if actors_version == 0 || actors_version > LAST_LEGACY_ACTORS_VERSION {
+        return Err(SignerError::UnsupportedCodeCid(code_name));
+    }
+
+    let hash = cid::multihash::Multihash::wrap(IDENTITY_HASH_CODE, code_name.as_bytes())?;
+
+    Ok(Cid::new_v1(RAW_CODEC, hash))
+}
+
+/// Return the actor name (e.g "fil/2/multisig") embedded in a legacy builtin actor code CID,
+/// or the string itself if it isn't such a CID.
+fn legacy_code_name(code_cid: &str) -> String {
+    match Cid::from_str(code_cid) {
+        Ok(cid) if cid.hash().code() == IDENTITY_HASH_CODE => {
+            String::from_utf8(cid.hash().digest().to_vec()).unwrap_or_else(|_| code_cid.to_string())
+        }
+        _ => code_cid.to_string(),
+    }
 }
 
 /// Deserialize Constructor Params
@@ -716,7 +2814,8 @@ pub fn deserialize_params(
 /// # Arguments
 ///
 /// * `params_b64_string` - The base64 params string;
-/// * `code_cid` - The string that tell the actor type which is being crated with this parameters;
+/// * `code_cid` - The string that tell the actor type which is being crated with this parameters
+///   (e.g "multisig", "fil/2/multisig" or the legacy code CID itself);
 pub fn deserialize_constructor_params(
     params_b64_string: String,
     code_cid: String,
@@ -724,30 +2823,37 @@ pub fn deserialize_constructor_params(
     let params_decode = base64::decode(params_b64_string)?;
     let serialized_params = RawBytes::new(params_decode);
 
-    if code_cid.as_str() == "multisig" {
-        let params = serialized_params.deserialize::<multisig::ConstructorParams>()?;
-        return Ok(MessageParams::MultisigConstructorParams(params));
-    }
-
-    if code_cid.as_str() == "paymentchannel" {
-        let params = serialized_params.deserialize::<paych::ConstructorParams>()?;
-        return Ok(MessageParams::PaychConstructorParams(params.into()));
-    }
-
-    if code_cid.as_str() == "fil/1/multisig" {
-        let deprecated_multisig_params = serialized_params.deserialize::<ConstructorParamsV1>()?;
-        let params = multisig::ConstructorParams {
-            signers: deprecated_multisig_params.signers,
-            num_approvals_threshold: deprecated_multisig_params.num_approvals_threshold,
-            unlock_duration: deprecated_multisig_params.unlock_duration,
-            start_epoch: 0,
-        };
-        return Ok(MessageParams::MultisigConstructorParams(params));
+    match legacy_code_name(&code_cid).as_str() {
+        // `start_epoch` was added in v2, the layout hasn't changed since
+        "multisig" | "fil/2/multisig" | "fil/3/multisig" | "fil/4/multisig" | "fil/5/multisig"
+        | "fil/6/multisig" | "fil/7/multisig" => {
+            let params = serialized_params.deserialize::<multisig::ConstructorParams>()?;
+            Ok(MessageParams::MultisigConstructorParams(params))
+        }
+        "fil/1/multisig" => {
+            let deprecated_multisig_params =
+                serialized_params.deserialize::<ConstructorParamsV1>()?;
+            let params = multisig::ConstructorParams {
+                signers: deprecated_multisig_params.signers,
+                num_approvals_threshold: deprecated_multisig_params.num_approvals_threshold,
+                unlock_duration: deprecated_multisig_params.unlock_duration,
+                start_epoch: 0,
+            };
+            Ok(MessageParams::MultisigConstructorParams(params))
+        }
+        "paymentchannel"
+        | "fil/1/paymentchannel"
+        | "fil/2/paymentchannel"
+        | "fil/3/paymentchannel"
+        | "fil/4/paymentchannel"
+        | "fil/5/paymentchannel"
+        | "fil/6/paymentchannel"
+        | "fil/7/paymentchannel" => {
+            let params = serialized_params.deserialize::<paych::ConstructorParams>()?;
+            Ok(MessageParams::PaychConstructorParams(params.into()))
+        }
+        _ => Err(SignerError::UnsupportedCodeCid(code_cid)),
     }
-
-    Err(SignerError::GenericString(
-        "Code CID not supported.".to_string(),
-    ))
 }
 
 /// Verify Voucher signature
@@ -777,13 +2883,13 @@ pub fn verify_voucher_signature(
                 let recovery_id = libsecp256k1::RecoveryId::parse(signature.bytes()[64])?;
                 let message = libsecp256k1::Message::parse(&digest);
                 let public_key = libsecp256k1::recover(&message, &sig, &recovery_id)?;
-                let mut signer = Address::new_secp256k1(public_key.serialize().as_ref())?;
-                signer.set_network(address.network());
+                let signer = Address::new_secp256k1(public_key.serialize().as_ref())?;
+
+                // Constant-time comparison of protocol and payload, the network is not relevant
+                let same_signer: bool = signer.to_bytes().ct_eq(&address.to_bytes()).into();
 
-                if signer.to_string() != address.to_string() {
-                    Err(SignerError::GenericString(
-                        "Address recovered doesn't match address given".to_string(),
-                    ))
+                if !same_signer {
+                    Err(SignerError::AddressMismatch)
                 } else {
                     Ok(libsecp256k1::verify(&message, &sig, &public_key))
                 }
@@ -794,14 +2900,72 @@ pub fn verify_voucher_signature(
 
                 Ok(pk.verify(sig, digest))
             }
-            _ => Err(SignerError::GenericString(
-                "Address should BLS or Secp256k1.".to_string(),
-            )),
+            _ => Err(SignerError::UnknownSigningProtocol),
         },
-        None => Err(SignerError::GenericString(
-            "Voucher not signed.".to_string(),
-        )),
+        None => Err(SignerError::VoucherNotSigned),
+    }
+}
+
+/// Recover the address of the signer of a secp256k1 signed voucher
+///
+/// # Arguments
+///
+/// * `voucher_base64_string` - The signed voucher as a base64 string;
+/// * `network` - The network of the returned address;
+pub fn recover_voucher_signer(
+    voucher_base64_string: String,
+    network: Network,
+) -> Result<String, SignerError> {
+    let decoded_voucher = base64::decode(voucher_base64_string)?;
+    let signed_voucher: paych::SignedVoucher = from_slice(&decoded_voucher)?;
+
+    let signature = signed_voucher
+        .signature
+        .as_ref()
+        .ok_or(SignerError::VoucherNotSigned)?;
+
+    if signature.sig_type != SignatureType::Secp256k1 {
+        return Err(SignerError::UnknownSigningProtocol);
     }
+    check_signature_length(signature)?;
+
+    let sv_bytes = signed_voucher
+        .signing_bytes()
+        .map_err(|err| SignerError::GenericString(err.to_string()))?;
+    let digest = utils::get_digest_voucher(&sv_bytes)?;
+
+    let sig = libsecp256k1::Signature::parse_standard_slice(&signature.bytes()[..64])?;
+    let recovery_id = libsecp256k1::RecoveryId::parse(signature.bytes()[64])?;
+    let message = libsecp256k1::Message::parse(&digest);
+    let public_key = libsecp256k1::recover(&message, &sig, &recovery_id)?;
+
+    let mut signer = Address::new_secp256k1(public_key.serialize().as_ref())?;
+    signer.set_network(network);
+
+    Ok(signer.to_string())
+}
+
+/// Check whether a voucher can be redeemed at the given epoch. Return a boolean.
+///
+/// The voucher is ready when `current_epoch` is at least `min_settle_height` and
+/// within the voucher time locks (a `time_lock_max` of 0 means no upper bound).
+///
+/// # Arguments
+///
+/// * `voucher_base64_string` - The voucher as a base64 string;
+/// * `current_epoch` - The current chain epoch;
+pub fn voucher_settle_ready(
+    voucher_base64_string: String,
+    current_epoch: ChainEpoch,
+) -> Result<bool, SignerError> {
+    let decoded_voucher = base64::decode(voucher_base64_string)?;
+    let voucher: paych::SignedVoucher = from_slice(&decoded_voucher)?;
+
+    let settle_height_reached = current_epoch >= voucher.min_settle_height;
+    let time_lock_min_reached = current_epoch >= voucher.time_lock_min;
+    let time_lock_max_valid = voucher.time_lock_max == 0 || current_epoch <= voucher.time_lock_max;
+
+    Ok(settle_height_reached && time_lock_min_reached && time_lock_max_valid)
 }
 
 /// Serialize voucher
@@ -830,14 +2994,7 @@ pub fn deserialize_voucher(
     Ok(SignedVoucherWrapper(signed_voucher))
 }
 
-/// Compute proposal hash
-///
-/// # Arguments
-///
-/// * `proposal_hash_data` - The proposal hash;
-pub fn compute_proposal_hash(
-    proposal_hash_data: ProposalHashDataAPI,
-) -> Result<String, SignerError> {
+fn proposal_hash(proposal_hash_data: &ProposalHashDataAPI) -> Result<[u8; 32], SignerError> {
     let proposal_data = multisig::ProposalHashData {
         requester: proposal_hash_data.requester.as_ref(),
         to: &proposal_hash_data.to,
@@ -848,27 +3005,165 @@ pub fn compute_proposal_hash(
 
     let serialize_proposal_data = RawBytes::serialize(proposal_data)
         .map_err(|err| SignerError::GenericString(err.to_string()))?;
-    let proposal_hash = utils::blake2b_256(&serialize_proposal_data);
+
+    Ok(utils::blake2b_256(&serialize_proposal_data))
+}
+
+/// Compute proposal hash
+///
+/// # Arguments
+///
+/// * `proposal_hash_data` - The proposal hash;
+pub fn compute_proposal_hash(
+    proposal_hash_data: ProposalHashDataAPI,
+) -> Result<String, SignerError> {
+    let proposal_hash = proposal_hash(&proposal_hash_data)?;
 
     Ok(base64::encode(proposal_hash))
 }
 
+/// Verify that a proposal hash (e.g from Approve or Cancel params) matches the proposal.
+/// Return a boolean.
+///
+/// # Arguments
+///
+/// * `proposal` - The proposal data;
+/// * `expected_b64` - The base64 proposal hash to check;
+pub fn verify_proposal_hash(
+    proposal: ProposalHashDataAPI,
+    expected_b64: &str,
+) -> Result<bool, SignerError> {
+    let expected = base64::decode(expected_b64)?;
+    let proposal_hash = proposal_hash(&proposal)?;
+
+    Ok(proposal_hash[..].ct_eq(&expected[..]).into())
+}
+
+/// Check if the number of distinct approvers that are multisig signers reaches the threshold.
+/// Duplicated approvals and approvals from non-signers are ignored. Addresses are compared
+/// regardless of their network.
+///
+/// # Arguments
+///
+/// * `signers` - The multisig signers;
+/// * `approvals` - The addresses that approved the transaction;
+/// * `threshold` - The number of approvals required;
+pub fn multisig_threshold_met(signers: &[Address], approvals: &[Address], threshold: u64) -> bool {
+    let mut approvers = Vec::new();
+
+    for approval in approvals {
+        let is_signer = signers
+            .iter()
+            .any(|signer| signer.payload() == approval.payload());
+
+        if is_signer && !approvers.contains(&approval.payload()) {
+            approvers.push(approval.payload());
+        }
+    }
+
+    approvers.len() as u64 >= threshold
+}
+
 /// Return the CID of a message
 ///
 /// # Arguments
 ///
 /// * `message_api` - The message;
 pub fn get_cid(message_api: MessageTxAPI) -> Result<String, SignerError> {
-    match message_api {
-        MessageTxAPI::Message(message) => {
-            let cid = message.cid()?;
+    let cid = get_cid_raw(&message_api)?;
 
-            Ok(cid.to_string())
-        }
-        MessageTxAPI::SignedMessage(signed_message) => {
-            let cid = signed_message.cid()?;
+    Ok(cid.to_string())
+}
 
-            Ok(cid.to_string())
-        }
+/// Return the CID of a message as a `Cid`
+///
+/// # Arguments
+///
+/// * `message_api` - The message;
+pub fn get_cid_raw(message_api: &MessageTxAPI) -> Result<Cid, SignerError> {
+    let cid = match message_api {
+        MessageTxAPI::Message(message) => message.cid()?,
+        MessageTxAPI::SignedMessage(signed_message) => signed_message.cid()?,
+    };
+
+    Ok(cid)
+}
+
+/// Return the binary representation of the CID of a message
+///
+/// # Arguments
+///
+/// * `message_api` - The message;
+pub fn get_cid_bytes(message_api: &MessageTxAPI) -> Result<Vec<u8>, SignerError> {
+    let cid = get_cid_raw(message_api)?;
+
+    Ok(cid.to_bytes())
+}
+
+/// Verify that a signed message matches the CID claimed for it. Return a boolean.
+///
+/// # Arguments
+///
+/// * `signed_message` - The signed message;
+/// * `claimed_cid` - The CID string announced for the signed message;
+pub fn verify_message_cid(
+    signed_message: &SignedMessage,
+    claimed_cid: &str,
+) -> Result<bool, SignerError> {
+    let claimed_cid = Cid::from_str(claimed_cid)?;
+    let cid = signed_message.cid()?;
+
+    Ok(cid == claimed_cid)
+}
+
+/// Return the CIDs of many messages, computed in parallel
+///
+/// # Arguments
+///
+/// * `messages` - The messages;
+pub fn cids_of_messages(messages: &[Message]) -> Result<Vec<Cid>, SignerError> {
+    messages
+        .par_iter()
+        .map(|message| message.cid().map_err(SignerError::from))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_one() -> PrivateKey {
+        let mut key = [0u8; SECRET_KEY_SIZE];
+        key[SECRET_KEY_SIZE - 1] = 1;
+        PrivateKey(key)
+    }
+
+    #[test]
+    fn rfc6979_known_answer() {
+        // Published secp256k1 RFC6979 vector: key 1, sha256("Satoshi Nakamoto")
+        let digest = <Sha256 as sha2::Digest>::digest(b"Satoshi Nakamoto");
+        let digest: [u8; 32] = digest.into();
+
+        let signature = sign_secp256k1_rfc6979(&digest, &key_one(), &[]).unwrap();
+
+        assert_eq!(
+            hex::encode(&signature.bytes[..64]),
+            "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8\
+             2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5"
+        );
+        assert_eq!(
+            signature,
+            sign_secp256k1_digest(&digest, &key_one()).unwrap()
+        );
+
+        // Same key and digest with 32 bytes of 0x01 as extra data (libsecp256k1 `ndata`),
+        // computed with an independent RFC6979 implementation
+        let signature = sign_secp256k1_rfc6979(&digest, &key_one(), &[1u8; 32]).unwrap();
+
+        assert_eq!(
+            hex::encode(&signature.bytes),
+            "bb6cf569458d507451271380d2863dad30355387836d5c3287a4efbd5ed1ad8e\
+             4bb4b7899e803f760fe89027e55f5d93768983d6e28af4b5722f6226b345380e01"
+        );
     }
 }
@@ -1,11 +1,64 @@
 use blake2b_simd::Params;
 use core::{array::TryFromSliceError, convert::TryInto};
+use fil_actor_paych::SignedVoucher;
+use fvm_ipld_encoding::from_slice;
+use fvm_shared::address::{Address, Network};
+use fvm_shared::crypto::signature::{Signature, SignatureType};
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::message::Message;
+use num_traits::Signed;
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+use std::str::FromStr;
+
+use crate::error::SignerError;
+use crate::SIGNATURE_RECOVERY_SIZE;
 
 static CID_PREFIX: &[u8] = &[0x01, 0x71, 0xa0, 0xe4, 0x02, 0x20];
 
+/// CBOR header of an array of 10 elements (unsigned message)
+const CBOR_MESSAGE_HEADER: u8 = 0x8a;
+/// CBOR header of an array of 2 elements (signed message)
+const CBOR_SIGNED_MESSAGE_HEADER: u8 = 0x82;
+
+/// Alphabet used by filecoin addresses string encoding (RFC4648 base32, lowercase, no padding)
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+/// Length of the checksum appended to the payload of an address string
+const ADDRESS_CHECKSUM_LEN: usize = 4;
+/// Protocol byte of delegated (f4) addresses
+const DELEGATED_PROTOCOL: u8 = 4;
+/// Namespace (actor ID) of the Ethereum address manager for delegated addresses
+pub const ETH_ADDRESS_MANAGER_NAMESPACE: u64 = 10;
+/// Length of an Ethereum address
+pub const ETH_ADDRESS_LEN: usize = 20;
+
+/// Number of decimals of FIL (1 FIL = 10^18 attoFIL)
+const FIL_DECIMALS: usize = 18;
+
+/// Scheme of payment request URIs
+const PAYMENT_REQUEST_SCHEME: &str = "filecoin:";
+
+/// Offset added to the recovery id by Ethereum (v = 27 or 28)
+const ETH_RECOVERY_OFFSET: u8 = 27;
+/// Offset added to twice the chain id and the recovery id by EIP-155 (v = chain_id * 2 + 35 + recovery)
+const EIP155_V_OFFSET: u64 = 35;
+
+/// Maximum length of a blake2b personalization string
+pub const BLAKE2B_PERSONAL_MAX_LEN: usize = blake2b_simd::PERSONALBYTES;
+
 pub fn blake2b_256(ingest: &[u8]) -> [u8; 32] {
+    blake2b_256_personal(ingest, &[])
+}
+
+/// blake2b 256 bits digest with a personalization string, used as domain separation tag.
+/// The personalization can be at most 16 bytes long (`BLAKE2B_PERSONAL_MAX_LEN`), shorter
+/// ones are padded with zeros, so an empty personalization is the same as none.
+///
+/// Panics if `personal` is longer than 16 bytes.
+pub fn blake2b_256_personal(ingest: &[u8], personal: &[u8]) -> [u8; 32] {
     let digest = Params::new()
         .hash_length(32)
+        .personal(personal)
         .to_state()
         .update(ingest)
         .finalize();
@@ -15,6 +68,13 @@ pub fn blake2b_256(ingest: &[u8]) -> [u8; 32] {
     ret
 }
 
+/// keccak256 hash (as used by Ethereum)
+pub fn keccak256(ingest: &[u8]) -> [u8; 32] {
+    let mut ret = [0u8; 32];
+    ret.copy_from_slice(&Keccak256::digest(ingest));
+    ret
+}
+
 /// transform a message into a hashed message ready to be signed and following Filecoin standard
 pub fn get_digest(message: &[u8]) -> Result<[u8; 32], TryFromSliceError> {
     let message_hashed = Params::new()
@@ -44,10 +104,556 @@ pub fn get_digest_voucher(message: &[u8]) -> Result<[u8; 32], TryFromSliceError>
     message_hashed.as_bytes().try_into()
 }
 
+/// check that the top-level CBOR looks like a message (array of 10 elements) or
+/// a signed message (array of 2 elements starting with a message), without fully decoding it
+pub fn is_valid_message_cbor(bytes: &[u8]) -> bool {
+    matches!(
+        bytes,
+        [CBOR_MESSAGE_HEADER, ..] | [CBOR_SIGNED_MESSAGE_HEADER, CBOR_MESSAGE_HEADER, ..]
+    )
+}
+
+/// sort messages by CID (their signing bytes) to get a canonical list, e.g. to compare the
+/// batches covered by aggregated signatures. Verification itself doesn't depend on the order.
+pub fn sort_messages_for_aggregation(messages: &mut [Message]) {
+    messages.sort_by_cached_key(|message| message.to_signing_bytes());
+}
+
+/// set the network of the `from` and `to` addresses of a message (e.g to resubmit a testnet
+/// message on mainnet)
+///
+/// Addresses inside the params don't need to be rewritten: the CBOR encoding of an address
+/// doesn't include the network. The params are left untouched, including opaque blobs such as
+/// the inner params of a multisig proposal. The signing bytes, and so the CID, don't change.
+pub fn message_set_network(message: &mut Message, network: Network) {
+    message.from.set_network(network);
+    message.to.set_network(network);
+}
+
+/// return the index pairs of messages sharing the same sender and nonce
+pub fn find_nonce_conflicts(messages: &[Message]) -> Vec<(usize, usize)> {
+    let mut conflicts = Vec::new();
+
+    for (i, message) in messages.iter().enumerate() {
+        for (j, other) in messages.iter().enumerate().skip(i + 1) {
+            if message.sequence == other.sequence && message.from.payload() == other.from.payload()
+            {
+                conflicts.push((i, j));
+            }
+        }
+    }
+
+    conflicts
+}
+
+/// return the checksum bits of a mnemonic (first `entropy bits / 32` bits of the entropy SHA256)
+pub fn mnemonic_checksum_bits(mnemonic: &str, language_code: &str) -> Result<u8, SignerError> {
+    let mnemonic = crate::parse_mnemonic(mnemonic, language_code)?;
+
+    let entropy = mnemonic.entropy();
+    let checksum_len = entropy.len() * 8 / 32;
+    let hash = Sha256::digest(entropy);
+
+    Ok(hash[0] >> (8 - checksum_len))
+}
+
+/// transform a secp256k1 signature (R || S || V) into the Ethereum recoverable form where V is 27 or 28
+pub fn to_eth_recoverable(
+    signature: &Signature,
+) -> Result<[u8; SIGNATURE_RECOVERY_SIZE], SignerError> {
+    if signature.sig_type != SignatureType::Secp256k1 {
+        return Err(SignerError::UnknownSigningProtocol);
+    }
+
+    let mut sig: [u8; SIGNATURE_RECOVERY_SIZE] = signature.bytes().try_into()?;
+    if sig[64] > 1 {
+        return Err(libsecp256k1::Error::InvalidRecoveryId.into());
+    }
+    sig[64] += ETH_RECOVERY_OFFSET;
+
+    Ok(sig)
+}
+
+/// transform an Ethereum recoverable signature (V is 27 or 28) into a filecoin secp256k1 signature
+pub fn from_eth_recoverable(sig: &[u8; SIGNATURE_RECOVERY_SIZE]) -> Result<Signature, SignerError> {
+    let v = sig[64];
+    if v != ETH_RECOVERY_OFFSET && v != ETH_RECOVERY_OFFSET + 1 {
+        return Err(libsecp256k1::Error::InvalidRecoveryId.into());
+    }
+
+    let mut bytes = sig.to_vec();
+    bytes[64] = v - ETH_RECOVERY_OFFSET;
+
+    Ok(Signature::new_secp256k1(bytes))
+}
+
+/// return the EIP-155 `v` value (chain_id * 2 + 35 + recovery) of a recovery id (0 or 1)
+pub fn to_eip155_v(recovery: u8, chain_id: u64) -> Result<u64, SignerError> {
+    eip155_v_base(chain_id)?
+        .checked_add(recovery as u64)
+        .ok_or(SignerError::ChainIdTooLarge(chain_id))
+}
+
+/// return the recovery id (0 or 1) of an EIP-155 `v` value, checking that it matches the chain id
+pub fn from_eip155_v(v: u64, chain_id: u64) -> Result<u8, SignerError> {
+    let base = eip155_v_base(chain_id)?;
+
+    match v.checked_sub(base) {
+        Some(recovery) if recovery <= 1 => Ok(recovery as u8),
+        _ => Err(SignerError::InvalidEip155V { v, chain_id }),
+    }
+}
+
+fn eip155_v_base(chain_id: u64) -> Result<u64, SignerError> {
+    chain_id
+        .checked_mul(2)
+        .and_then(|v| v.checked_add(EIP155_V_OFFSET))
+        .ok_or(SignerError::ChainIdTooLarge(chain_id))
+}
+
+/// split a secp256k1 signature (R || S || V) into its R, S and V components
+pub fn split_signature_rsv(signature: &Signature) -> Result<([u8; 32], [u8; 32], u8), SignerError> {
+    if signature.sig_type != SignatureType::Secp256k1 {
+        return Err(SignerError::UnknownSigningProtocol);
+    }
+
+    let bytes = signature.bytes();
+    if bytes.len() != SIGNATURE_RECOVERY_SIZE {
+        return Err(SignerError::InvalidSignatureLength(
+            SignatureType::Secp256k1,
+            SIGNATURE_RECOVERY_SIZE,
+            bytes.len(),
+        ));
+    }
+
+    let r: [u8; 32] = bytes[..32].try_into()?;
+    let s: [u8; 32] = bytes[32..64].try_into()?;
+
+    Ok((r, s, bytes[64]))
+}
+
+/// join R, S and V components into a secp256k1 signature (R || S || V)
+pub fn join_signature_rsv(r: &[u8; 32], s: &[u8; 32], v: u8) -> Result<Signature, SignerError> {
+    if v > 1 {
+        return Err(libsecp256k1::Error::InvalidRecoveryId.into());
+    }
+
+    let mut bytes = Vec::with_capacity(SIGNATURE_RECOVERY_SIZE);
+    bytes.extend_from_slice(r);
+    bytes.extend_from_slice(s);
+    bytes.push(v);
+
+    Ok(Signature::new_secp256k1(bytes))
+}
+
+fn address_checksum(ingest: &[u8]) -> [u8; ADDRESS_CHECKSUM_LEN] {
+    let digest = Params::new()
+        .hash_length(ADDRESS_CHECKSUM_LEN)
+        .to_state()
+        .update(ingest)
+        .finalize();
+
+    let mut ret = [0u8; ADDRESS_CHECKSUM_LEN];
+    ret.copy_from_slice(digest.as_bytes());
+    ret
+}
+
+fn base32_encode(input: &[u8]) -> String {
+    let mut encoded = String::with_capacity((input.len() * 8 + 4) / 5);
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+
+    for &byte in input {
+        buffer = (buffer << 8) | byte as u16;
+        bits += 8;
+
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[(buffer >> bits) as usize & 0x1f] as char);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[(buffer << (5 - bits)) as usize & 0x1f] as char);
+    }
+
+    encoded
+}
+
+fn base32_decode(input: &str) -> Result<Vec<u8>, SignerError> {
+    let mut decoded = Vec::with_capacity(input.len() * 5 / 8);
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+
+    for c in input.bytes() {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or_else(|| SignerError::GenericString("Invalid base32 character".to_string()))?;
+
+        buffer = (buffer << 5) | value as u16;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    Ok(decoded)
+}
+
+/// Parse a delegated (f4) address string into its network, namespace and subaddress.
+///
+/// `fvm_shared::address::Address` doesn't support the delegated protocol yet, so the string
+/// form is decoded here: `{f|t}4{namespace}f{base32(subaddress || checksum)}`.
+fn parse_delegated_address(address: &str) -> Result<(Network, u64, Vec<u8>), SignerError> {
+    let invalid = || SignerError::InvalidDelegatedAddress(address.to_string());
+
+    let network = match address.get(..1) {
+        Some("f") => Network::Mainnet,
+        Some("t") => Network::Testnet,
+        _ => return Err(invalid()),
+    };
+
+    if address.get(1..2) != Some("4") {
+        return Err(invalid());
+    }
+
+    let (namespace, encoded) = address[2..].split_once('f').ok_or_else(invalid)?;
+    let namespace: u64 = namespace.parse().map_err(|_| invalid())?;
+
+    let payload = base32_decode(encoded).map_err(|_| invalid())?;
+    if payload.len() < ADDRESS_CHECKSUM_LEN {
+        return Err(invalid());
+    }
+    let (subaddress, checksum) = payload.split_at(payload.len() - ADDRESS_CHECKSUM_LEN);
+
+    let mut address_bytes = vec![DELEGATED_PROTOCOL];
+    address_bytes.extend(leb128_encode(namespace));
+    address_bytes.extend_from_slice(subaddress);
+
+    if address_checksum(&address_bytes) != checksum {
+        return Err(invalid());
+    }
+
+    Ok((network, namespace, subaddress.to_vec()))
+}
+
+fn leb128_encode(mut value: u64) -> Vec<u8> {
+    let mut encoded = Vec::new();
+
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            encoded.push(byte);
+            return encoded;
+        }
+        encoded.push(byte | 0x80);
+    }
+}
+
+/// render a 20 bytes Ethereum address as an EIP-55 checksummed 0x string
+fn eth_checksummed(eth_address: &[u8]) -> String {
+    let lower = hex::encode(eth_address);
+    let hash = keccak256(lower.as_bytes());
+
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+            if c.is_ascii_alphabetic() && nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    format!("0x{}", checksummed)
+}
+
+/// return the EIP-55 checksummed 0x form of a f410 address (delegated address in the Ethereum address manager namespace)
+pub fn f410_to_eth_checksummed(address: &str) -> Result<String, SignerError> {
+    Ok(eth_checksummed(&f4_to_eth(address)?))
+}
+
+/// build the f410 address string (delegated address in the Ethereum address manager namespace) of an Ethereum address
+///
+/// `fvm_shared::address::Address` doesn't support the delegated protocol yet, so the string form is returned.
+pub fn eth_to_f4(
+    eth_addr: &[u8; ETH_ADDRESS_LEN],
+    network: Network,
+) -> Result<String, SignerError> {
+    let mut address_bytes = vec![DELEGATED_PROTOCOL];
+    address_bytes.extend(leb128_encode(ETH_ADDRESS_MANAGER_NAMESPACE));
+    address_bytes.extend_from_slice(eth_addr);
+
+    let mut payload = eth_addr.to_vec();
+    payload.extend_from_slice(&address_checksum(&address_bytes));
+
+    let prefix = match network {
+        Network::Mainnet => "f",
+        Network::Testnet => "t",
+    };
+
+    Ok(format!(
+        "{}{}{}f{}",
+        prefix,
+        DELEGATED_PROTOCOL,
+        ETH_ADDRESS_MANAGER_NAMESPACE,
+        base32_encode(&payload)
+    ))
+}
+
+/// return the 20 bytes Ethereum address of a f410 address, failing for other delegated namespaces
+pub fn f4_to_eth(address: &str) -> Result<[u8; ETH_ADDRESS_LEN], SignerError> {
+    let (_, namespace, subaddress) = parse_delegated_address(address)?;
+
+    if namespace != ETH_ADDRESS_MANAGER_NAMESPACE || subaddress.len() != ETH_ADDRESS_LEN {
+        return Err(SignerError::InvalidDelegatedAddress(address.to_string()));
+    }
+
+    Ok(subaddress[..].try_into()?)
+}
+
+/// re-encode an address string with the prefix of another network (`f` mainnet, `t` testnet)
+pub fn convert_address_network(address: &str, to: Network) -> Result<String, SignerError> {
+    // Delegated addresses can't be parsed by `Address`, the checksum doesn't cover the network
+    if address.get(1..2) == Some("4") {
+        parse_delegated_address(address)?;
+
+        let prefix = match to {
+            Network::Mainnet => "f",
+            Network::Testnet => "t",
+        };
+
+        return Ok(format!("{}{}", prefix, &address[1..]));
+    }
+
+    let mut address = Address::from_str(address)?;
+    address.set_network(to);
+
+    Ok(address.to_string())
+}
+
+/// re-encode a base64 signed voucher as hex
+pub fn voucher_base64_to_hex(b64: &str) -> Result<String, SignerError> {
+    let voucher_bytes = base64::decode(b64)?;
+    let _: SignedVoucher = from_slice(&voucher_bytes)?;
+
+    Ok(hex::encode(voucher_bytes))
+}
+
+/// re-encode a hex signed voucher as base64
+pub fn voucher_hex_to_base64(hex_voucher: &str) -> Result<String, SignerError> {
+    let voucher_bytes = hex::decode(hex_voucher)?;
+    let _: SignedVoucher = from_slice(&voucher_bytes)?;
+
+    Ok(base64::encode(voucher_bytes))
+}
+
+/// return the size in bytes of a base64 signed voucher
+pub fn voucher_size(voucher_base64: &str) -> Result<usize, SignerError> {
+    let voucher_bytes = base64::decode(voucher_base64)?;
+    let _: SignedVoucher = from_slice(&voucher_bytes)?;
+
+    Ok(voucher_bytes.len())
+}
+
+fn rlp_length_prefix(length: usize, offset: u8) -> Vec<u8> {
+    if length < 56 {
+        return vec![offset + length as u8];
+    }
+
+    let length_bytes: Vec<u8> = length
+        .to_be_bytes()
+        .iter()
+        .copied()
+        .skip_while(|b| *b == 0)
+        .collect();
+
+    let mut prefix = vec![offset + 55 + length_bytes.len() as u8];
+    prefix.extend(length_bytes);
+    prefix
+}
+
+/// RLP encoding of a byte string
+pub fn rlp_encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return bytes.to_vec();
+    }
+
+    let mut encoded = rlp_length_prefix(bytes.len(), 0x80);
+    encoded.extend_from_slice(bytes);
+    encoded
+}
+
+/// RLP encoding of an unsigned integer given as big endian bytes (leading zeros are dropped)
+pub fn rlp_encode_uint(bytes_be: &[u8]) -> Vec<u8> {
+    let first_non_zero = bytes_be
+        .iter()
+        .position(|b| *b != 0)
+        .unwrap_or(bytes_be.len());
+
+    rlp_encode_bytes(&bytes_be[first_non_zero..])
+}
+
+/// RLP encoding of a list of already RLP encoded items
+pub fn rlp_encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+
+    let mut encoded = rlp_length_prefix(payload.len(), 0xc0);
+    encoded.extend(payload);
+    encoded
+}
+
+/// format an attoFIL amount as a decimal FIL amount, without trailing zeros (e.g "1.5")
+pub fn format_fil(amount: &TokenAmount) -> String {
+    let digits = amount.abs().to_string();
+    let digits = format!("{:0>width$}", digits, width = FIL_DECIMALS + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - FIL_DECIMALS);
+    let fraction = fraction.trim_end_matches('0');
+
+    let sign = if amount.is_negative() { "-" } else { "" };
+    if fraction.is_empty() {
+        format!("{}{}", sign, integer)
+    } else {
+        format!("{}{}.{}", sign, integer, fraction)
+    }
+}
+
+fn percent_encode(input: &str) -> String {
+    input
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn percent_decode(input: &str) -> Result<String, SignerError> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex_byte = input.get(i + 1..i + 3).ok_or_else(|| {
+                SignerError::GenericString("Invalid percent encoding".to_string())
+            })?;
+            decoded.push(u8::from_str_radix(hex_byte, 16)?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(decoded).map_err(|err| SignerError::GenericString(err.to_string()))
+}
+
+fn parse_payment_amount(amount: &str) -> Result<TokenAmount, SignerError> {
+    let amount = TokenAmount::from_str(amount)?;
+
+    if amount.is_negative() {
+        return Err(SignerError::NegativeAmount("amount".to_string()));
+    }
+
+    Ok(amount)
+}
+
+/// build a `filecoin:<address>?amount=<attoFIL>&label=<label>` payment request URI (BIP21 like)
+pub fn payment_request_uri(
+    address: &str,
+    amount: Option<&TokenAmount>,
+    label: Option<&str>,
+) -> Result<String, SignerError> {
+    Address::from_str(address)?;
+
+    let mut params = Vec::new();
+    if let Some(amount) = amount {
+        if amount.is_negative() {
+            return Err(SignerError::NegativeAmount("amount".to_string()));
+        }
+        params.push(format!("amount={}", amount));
+    }
+    if let Some(label) = label {
+        params.push(format!("label={}", percent_encode(label)));
+    }
+
+    let mut uri = format!("{}{}", PAYMENT_REQUEST_SCHEME, address);
+    if !params.is_empty() {
+        uri.push('?');
+        uri.push_str(&params.join("&"));
+    }
+
+    Ok(uri)
+}
+
+/// parse a payment request URI into its address, amount (attoFIL) and label
+pub fn parse_payment_request_uri(
+    uri: &str,
+) -> Result<(String, Option<TokenAmount>, Option<String>), SignerError> {
+    let request = uri
+        .strip_prefix(PAYMENT_REQUEST_SCHEME)
+        .ok_or_else(|| SignerError::GenericString("Not a filecoin payment request".to_string()))?;
+
+    let (address, query) = match request.split_once('?') {
+        Some((address, query)) => (address, Some(query)),
+        None => (request, None),
+    };
+    Address::from_str(address)?;
+
+    let mut amount = None;
+    let mut label = None;
+
+    for param in query.into_iter().flat_map(|query| query.split('&')) {
+        let (key, value) = param.split_once('=').unwrap_or((param, ""));
+        match key {
+            "amount" => amount = Some(parse_payment_amount(value)?),
+            "label" => label = Some(percent_decode(value)?),
+            // unknown required parameters must be rejected, others are ignored
+            _ if key.starts_with("req-") => {
+                return Err(SignerError::GenericString(format!(
+                    "Unsupported required parameter `{}`",
+                    key
+                )))
+            }
+            _ => {}
+        }
+    }
+
+    Ok((address.to_string(), amount, label))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::utils::get_digest;
+    use crate::error::SignerError;
+    use crate::utils::{
+        blake2b_256, blake2b_256_personal, convert_address_network, eth_to_f4,
+        f410_to_eth_checksummed, f4_to_eth, find_nonce_conflicts, format_fil, from_eip155_v,
+        from_eth_recoverable, get_digest, is_valid_message_cbor, join_signature_rsv, keccak256,
+        message_set_network, mnemonic_checksum_bits, parse_payment_request_uri,
+        payment_request_uri, rlp_encode_bytes, rlp_encode_list, rlp_encode_uint,
+        split_signature_rsv, to_eip155_v, to_eth_recoverable, voucher_base64_to_hex,
+        voucher_hex_to_base64, voucher_size,
+    };
+    use fvm_ipld_encoding::RawBytes;
+    use fvm_shared::address::{Address, Network};
+    use fvm_shared::crypto::signature::Signature;
+    use fvm_shared::econ::TokenAmount;
+    use fvm_shared::message::Message;
     use hex::{decode, encode};
+    use std::convert::TryInto;
+    use std::str::FromStr;
 
     #[test]
     fn test_digest_message() {
@@ -62,6 +668,376 @@ mod tests {
         );
     }
 
+    #[test]
+    fn blake2b_personalization() {
+        let data = b"filecoin";
+
+        assert_eq!(blake2b_256(data), blake2b_256_personal(data, &[]));
+        assert_ne!(
+            blake2b_256(data),
+            blake2b_256_personal(data, b"fil-context")
+        );
+        assert_ne!(
+            blake2b_256_personal(data, b"fil-context"),
+            blake2b_256_personal(data, b"fil-other")
+        );
+    }
+
+    #[test]
+    fn message_cbor_structure() {
+        const MESSAGE_CBOR: &str = "8a005501fd1d0f4dfcd7e99afcb99a8326b7dc459d32c6285501b882619d46558f3d9e316d11b48dcf211327025a0144000186a01961a84200014200010040";
+        const SIGNED_MESSAGE_CBOR: &str = "828a005501fd1d0f4dfcd7e99afcb99a8326b7dc459d32c62855011eaf1c8a4bbfeeb0870b1745b1f57503470b71160144000186a01909c4420001420001004058420106398485060ca2a4deb97027f518f45569360c3873a4303926fa6909a7299d4c55883463120836358ff3396882ee0dc2cf15961bd495cdfb3de1ee2e8bd3768e01";
+
+        assert!(is_valid_message_cbor(&decode(MESSAGE_CBOR).unwrap()));
+        assert!(is_valid_message_cbor(&decode(SIGNED_MESSAGE_CBOR).unwrap()));
+
+        assert!(!is_valid_message_cbor(&[]));
+        assert!(!is_valid_message_cbor(&[0xa0]));
+        assert!(!is_valid_message_cbor(&[0x82, 0x01, 0x02]));
+        assert!(!is_valid_message_cbor(&[0x88, 0x00]));
+    }
+
+    #[test]
+    fn eth_recoverable_round_trip() {
+        let mut bytes = vec![0x11; 65];
+        bytes[64] = 1;
+        let signature = Signature::new_secp256k1(bytes.clone());
+
+        let eth_sig = to_eth_recoverable(&signature).unwrap();
+        assert_eq!(eth_sig[64], 28);
+        assert_eq!(eth_sig[..64], bytes[..64]);
+
+        let recovered = from_eth_recoverable(&eth_sig).unwrap();
+        assert_eq!(recovered.bytes(), &bytes[..]);
+
+        let mut invalid = eth_sig;
+        invalid[64] = 1;
+        assert!(from_eth_recoverable(&invalid).is_err());
+
+        assert!(to_eth_recoverable(&Signature::new_bls(vec![0; 96])).is_err());
+    }
+
+    #[test]
+    fn signature_rsv_round_trip() {
+        // signature of the signed message used in `message_cbor_structure`
+        let bytes = decode("06398485060ca2a4deb97027f518f45569360c3873a4303926fa6909a7299d4c55883463120836358ff3396882ee0dc2cf15961bd495cdfb3de1ee2e8bd3768e01").unwrap();
+        let signature = Signature::new_secp256k1(bytes.clone());
+
+        let (r, s, v) = split_signature_rsv(&signature).unwrap();
+        assert_eq!(r[..], bytes[..32]);
+        assert_eq!(s[..], bytes[32..64]);
+        assert_eq!(v, 1);
+
+        let joined = join_signature_rsv(&r, &s, v).unwrap();
+        assert_eq!(joined, signature);
+
+        assert!(join_signature_rsv(&r, &s, 27).is_err());
+        assert!(split_signature_rsv(&Signature::new_secp256k1(bytes[..64].to_vec())).is_err());
+        assert!(split_signature_rsv(&Signature::new_bls(vec![0; 96])).is_err());
+    }
+
+    #[test]
+    fn eth_f4_round_trip() {
+        let eth_address: [u8; 20] = decode("d8da6bf26964af9d7eed9e03e53415d37aa96045")
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let f4_mainnet = eth_to_f4(&eth_address, Network::Mainnet).unwrap();
+        let f4_testnet = eth_to_f4(&eth_address, Network::Testnet).unwrap();
+        assert_eq!(f4_mainnet, "f410f3dngx4tjmsxz27xntyb6knav2n5ksycf6tzrcna");
+        assert_eq!(f4_testnet, "t410f3dngx4tjmsxz27xntyb6knav2n5ksycf6tzrcna");
+
+        assert_eq!(f4_to_eth(&f4_mainnet).unwrap(), eth_address);
+        assert_eq!(f4_to_eth(&f4_testnet).unwrap(), eth_address);
+
+        // Not the Ethereum address manager namespace
+        assert!(matches!(
+            f4_to_eth("f432fkkld55ioe7qg24wvt7fu6pbknb56ht7p7xy5iby"),
+            Err(SignerError::InvalidDelegatedAddress(_))
+        ));
+        // Not a delegated address
+        assert!(matches!(
+            f4_to_eth("f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba"),
+            Err(SignerError::InvalidDelegatedAddress(_))
+        ));
+    }
+
+    #[test]
+    fn eip155_v_values() {
+        // Filecoin mainnet
+        assert_eq!(to_eip155_v(0, 314).unwrap(), 663);
+        assert_eq!(to_eip155_v(1, 314).unwrap(), 664);
+        assert_eq!(from_eip155_v(663, 314).unwrap(), 0);
+        assert_eq!(from_eip155_v(664, 314).unwrap(), 1);
+
+        // Calibration network
+        assert_eq!(to_eip155_v(0, 314159).unwrap(), 628353);
+        assert_eq!(to_eip155_v(1, 314159).unwrap(), 628354);
+        assert_eq!(from_eip155_v(628353, 314159).unwrap(), 0);
+        assert_eq!(from_eip155_v(628354, 314159).unwrap(), 1);
+
+        // Another chain id, or not an EIP-155 value
+        assert!(from_eip155_v(663, 314159).is_err());
+        assert!(from_eip155_v(665, 314).is_err());
+        assert!(from_eip155_v(27, 314).is_err());
+
+        // Chain id overflowing the `v` value
+        assert!(matches!(
+            to_eip155_v(1, u64::MAX / 2 - 17),
+            Err(SignerError::ChainIdTooLarge(_))
+        ));
+        assert!(matches!(
+            from_eip155_v(u64::MAX, u64::MAX / 2),
+            Err(SignerError::ChainIdTooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn f410_to_eth() {
+        assert_eq!(
+            f410_to_eth_checksummed("f410fkkld55ioe7qg24wvt7fu6pbknb56ht7pt4zamxa").unwrap(),
+            "0x52963EF50e27e06D72D59fcB4F3c2a687BE3cfEf"
+        );
+        assert_eq!(
+            f410_to_eth_checksummed("t410fkkld55ioe7qg24wvt7fu6pbknb56ht7pt4zamxa").unwrap(),
+            "0x52963EF50e27e06D72D59fcB4F3c2a687BE3cfEf"
+        );
+
+        // Bad checksum
+        assert!(f410_to_eth_checksummed("f410fkkld55ioe7qg24wvt7fu6pbknb56ht7pt4zamxb").is_err());
+        // Not a delegated address
+        assert!(f410_to_eth_checksummed("f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").is_err());
+        // Not the Ethereum address manager namespace
+        assert!(f410_to_eth_checksummed("f432fkkld55ioe7qg24wvt7fu6pbknb56ht7p7xy5iby").is_err());
+    }
+
+    #[test]
+    fn address_network_conversion() {
+        let addresses = [
+            "01004",
+            "1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba",
+            "24acjqhdetck7irsvmn2p6jpuwnouzjxuoa22rva",
+            "3uxb75vcy3ilwbsaavao52v7gfnfh6aics4a7nj26dwpcmj4mxxgnzholkupuplafdrbd55frpoolfnm7wlda",
+            "410fkkld55ioe7qg24wvt7fu6pbknb56ht7pt4zamxa",
+        ];
+
+        for address in addresses {
+            let mainnet = format!("f{}", address);
+            let testnet = format!("t{}", address);
+
+            assert_eq!(
+                convert_address_network(&mainnet, Network::Testnet).unwrap(),
+                testnet
+            );
+            assert_eq!(
+                convert_address_network(&testnet, Network::Mainnet).unwrap(),
+                mainnet
+            );
+            // Already on the requested network
+            assert_eq!(
+                convert_address_network(&mainnet, Network::Mainnet).unwrap(),
+                mainnet
+            );
+        }
+
+        assert!(convert_address_network(
+            "f410fkkld55ioe7qg24wvt7fu6pbknb56ht7pt4zamxb",
+            Network::Testnet
+        )
+        .is_err());
+        assert!(convert_address_network(
+            "x1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba",
+            Network::Testnet
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn mnemonic_checksum() {
+        let mnemonic_12 = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert_eq!(mnemonic_checksum_bits(mnemonic_12, "en").unwrap(), 3);
+
+        let mnemonic_24 = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+        assert_eq!(mnemonic_checksum_bits(mnemonic_24, "en").unwrap(), 0x66);
+
+        assert!(matches!(
+            mnemonic_checksum_bits(mnemonic_12, "xx"),
+            Err(SignerError::UnknownLanguageCode(_))
+        ));
+        assert!(matches!(
+            mnemonic_checksum_bits("abandon abandon abandon", "en"),
+            Err(SignerError::InvalidMnemonicWordCount(3))
+        ));
+    }
+
+    #[test]
+    fn voucher_hex_round_trip() {
+        const VOUCHER_BASE64: &str = "i0MA8gcAAED2AAFEAAGGoACAWEIBayRmYQQCatrELBc2rwfu0jJk0EmVr+eVccDsThtM1ZVzkrC53a6qVgrgFkB8OHoiZSlNmW/nmCU7G2POhEeo2gE=";
+        const VOUCHER_HEX: &str = "8b4300f207000040f6000144000186a000805842016b24666104026adac42c1736af07eed23264d04995afe79571c0ec4e1b4cd5957392b0b9ddaeaa560ae016407c387a2265294d996fe798253b1b63ce8447a8da01";
+
+        assert_eq!(voucher_base64_to_hex(VOUCHER_BASE64).unwrap(), VOUCHER_HEX);
+        assert_eq!(voucher_hex_to_base64(VOUCHER_HEX).unwrap(), VOUCHER_BASE64);
+
+        assert!(voucher_hex_to_base64("8a00").is_err());
+
+        assert_eq!(voucher_size(VOUCHER_BASE64).unwrap(), VOUCHER_HEX.len() / 2);
+        assert!(voucher_size("igA=").is_err());
+    }
+
+    #[test]
+    fn nonce_conflicts() {
+        let message = Message {
+            version: 0,
+            to: Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+            from: Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap(),
+            sequence: 1,
+            value: TokenAmount::from(100),
+            gas_limit: 25000,
+            gas_fee_cap: TokenAmount::from(2500),
+            gas_premium: TokenAmount::from(2500),
+            method_num: 0,
+            params: RawBytes::new(vec![]),
+        };
+
+        let messages = vec![
+            message.clone(),
+            Message {
+                sequence: 2,
+                ..message.clone()
+            },
+            Message {
+                from: Address::from_str("f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap(),
+                ..message.clone()
+            },
+            Message {
+                from: Address::from_str("t137sjdbgunloi7couiy4l5nc7pd6k2jmq32vizpy").unwrap(),
+                ..message.clone()
+            },
+            Message {
+                value: TokenAmount::from(5),
+                ..message
+            },
+        ];
+
+        assert_eq!(
+            find_nonce_conflicts(&messages),
+            vec![(0, 2), (0, 4), (2, 4)]
+        );
+        assert!(find_nonce_conflicts(&messages[..2]).is_empty());
+    }
+
+    #[test]
+    fn message_network_change() {
+        let mut message = Message {
+            version: 0,
+            to: Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+            from: Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap(),
+            sequence: 1,
+            value: TokenAmount::from(100),
+            gas_limit: 25000,
+            gas_fee_cap: TokenAmount::from(2500),
+            gas_premium: TokenAmount::from(2500),
+            method_num: 0,
+            params: RawBytes::new(vec![]),
+        };
+        let signing_bytes = message.to_signing_bytes();
+
+        message_set_network(&mut message, Network::Mainnet);
+
+        assert_eq!(
+            message.to.to_string(),
+            "f17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy"
+        );
+        assert_eq!(
+            message.from.to_string(),
+            "f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba"
+        );
+        assert_eq!(message.to_signing_bytes(), signing_bytes);
+
+        message_set_network(&mut message, Network::Testnet);
+        assert_eq!(
+            message.from.to_string(),
+            "t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba"
+        );
+    }
+
+    #[test]
+    fn keccak256_digest() {
+        assert_eq!(
+            encode(keccak256(b"foo")),
+            "41b1a0649752af1b28b3dc29a1556eee781e4a4c3a1f7f53f90fa834de098c4d"
+        );
+    }
+
+    #[test]
+    fn rlp_encoding() {
+        assert_eq!(rlp_encode_bytes(b"dog"), vec![0x83, b'd', b'o', b'g']);
+        assert_eq!(rlp_encode_bytes(&[0x0f]), vec![0x0f]);
+        assert_eq!(rlp_encode_bytes(&[]), vec![0x80]);
+        assert_eq!(rlp_encode_uint(&0u64.to_be_bytes()), vec![0x80]);
+        assert_eq!(
+            rlp_encode_uint(&1024u64.to_be_bytes()),
+            vec![0x82, 0x04, 0x00]
+        );
+        assert_eq!(rlp_encode_list(&[]), vec![0xc0]);
+        assert_eq!(
+            rlp_encode_list(&[rlp_encode_bytes(b"cat"), rlp_encode_bytes(b"dog")]),
+            vec![0xc8, 0x83, b'c', b'a', b't', 0x83, b'd', b'o', b'g']
+        );
+
+        let long_string = [b'a'; 56];
+        assert_eq!(rlp_encode_bytes(&long_string)[..2], [0xb8, 56]);
+    }
+
+    #[test]
+    fn fil_formatting() {
+        assert_eq!(format_fil(&TokenAmount::from(0)), "0");
+        assert_eq!(format_fil(&TokenAmount::from(1)), "0.000000000000000001");
+        assert_eq!(
+            format_fil(&TokenAmount::from(1_500_000_000_000_000_000u64)),
+            "1.5"
+        );
+        assert_eq!(
+            format_fil(&TokenAmount::from(-2_000_000_000_000_000_000i64)),
+            "-2"
+        );
+    }
+
+    #[test]
+    fn payment_request_round_trip() {
+        let address = "f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba";
+        let amount = TokenAmount::from(1_000_000_000_000_000_000u64);
+
+        let uri = payment_request_uri(address, Some(&amount), Some("coffee & cake")).unwrap();
+        assert_eq!(
+            uri,
+            "filecoin:f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba?amount=1000000000000000000&label=coffee%20%26%20cake"
+        );
+
+        let (parsed_address, parsed_amount, parsed_label) =
+            parse_payment_request_uri(&uri).unwrap();
+        assert_eq!(parsed_address, address);
+        assert_eq!(parsed_amount, Some(amount));
+        assert_eq!(parsed_label.as_deref(), Some("coffee & cake"));
+
+        assert_eq!(
+            payment_request_uri(address, None, None).unwrap(),
+            "filecoin:f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba"
+        );
+
+        assert!(payment_request_uri("f1invalid", None, None).is_err());
+        assert!(matches!(
+            payment_request_uri(address, Some(&TokenAmount::from(-1)), None),
+            Err(SignerError::NegativeAmount(_))
+        ));
+        assert!(
+            parse_payment_request_uri("bitcoin:f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").is_err()
+        );
+        assert!(parse_payment_request_uri(&format!("filecoin:{}?amount=-5", address)).is_err());
+        assert!(parse_payment_request_uri(&format!("{}&req-memo=x", uri)).is_err());
+    }
+
     #[test]
     fn empty() {
         // FIXME:
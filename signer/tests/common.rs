@@ -1,6 +1,15 @@
+use bls_signatures::Serialize;
+use fvm_ipld_encoding::{Cbor, RawBytes};
+use fvm_shared::address::Address;
+use fvm_shared::crypto::signature::Signature;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::message::Message;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 use serde_json::{Result, Value};
 use std::fs::File;
 use std::io::Read;
+use std::str::FromStr;
 
 pub fn load_test_vectors(path: &str) -> Result<Value> {
     /* Load test vectors */
@@ -12,3 +21,55 @@ pub fn load_test_vectors(path: &str) -> Result<Value> {
 
     Ok(v)
 }
+
+/// BLS keys each sending one message, with the aggregated signature of the messages
+pub struct BlsAggregateFixture {
+    pub private_keys: Vec<bls_signatures::PrivateKey>,
+    pub messages: Vec<Message>,
+    pub cbor_messages: Vec<Vec<u8>>,
+    pub signature: Signature,
+}
+
+/// Build a BLS aggregation fixture with `count` keys generated from `seed`
+pub fn bls_aggregate_fixture(seed: u64, count: usize) -> BlsAggregateFixture {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+    let private_keys: Vec<_> = (0..count)
+        .map(|_| bls_signatures::PrivateKey::generate(&mut rng))
+        .collect();
+
+    let messages: Vec<Message> = private_keys
+        .iter()
+        .map(|private_key| Message {
+            version: 0,
+            to: Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+            from: Address::new_bls(&private_key.public_key().as_bytes()).unwrap(),
+            sequence: 1,
+            value: TokenAmount::from_str("100000").unwrap(),
+            gas_limit: 25000,
+            gas_fee_cap: TokenAmount::from_str("2500").unwrap(),
+            gas_premium: TokenAmount::from_str("2500").unwrap(),
+            method_num: 0,
+            params: RawBytes::new(vec![]),
+        })
+        .collect();
+
+    let cbor_messages: Vec<Vec<u8>> = messages
+        .iter()
+        .map(|message| message.marshal_cbor().unwrap())
+        .collect();
+
+    let sigs: Vec<bls_signatures::Signature> = messages
+        .iter()
+        .zip(private_keys.iter())
+        .map(|(message, private_key)| private_key.sign(message.to_signing_bytes()))
+        .collect();
+    let aggregated_signature = bls_signatures::aggregate(&sigs).unwrap();
+
+    BlsAggregateFixture {
+        private_keys,
+        messages,
+        cbor_messages,
+        signature: Signature::new_bls(aggregated_signature.as_bytes()),
+    }
+}
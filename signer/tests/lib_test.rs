@@ -1,4 +1,4 @@
-use std::convert::TryFrom;
+use std::convert::{TryFrom, TryInto};
 use std::str::FromStr;
 
 use bip39::{Language, Seed};
@@ -8,12 +8,14 @@ use fvm_shared::message::Message;
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
 use rayon::prelude::*;
+use zeroize::Zeroize;
 
 use fil_actor_multisig as multisig;
-use filecoin_signer::api::{MessageParams, MessageTxAPI};
+use filecoin_signer::api::{ActorType, MessageParams, MessageTxAPI};
+use filecoin_signer::error::SignerError;
 use filecoin_signer::*;
-use fvm_shared::address::Address;
-use fvm_shared::crypto::signature::Signature;
+use fvm_shared::address::{Address, Network};
+use fvm_shared::crypto::signature::{Signature, SignatureType};
 use fvm_shared::econ::TokenAmount;
 
 mod common;
@@ -94,6 +96,212 @@ fn derive_key_from_seed() {
     );
 }
 
+#[test]
+fn neuter_extended_key() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let mnemonic = test_value["mnemonic"].as_str().unwrap();
+    let language_code = test_value["language_code"].as_str().unwrap();
+
+    let extended_key = key_derive(mnemonic, "m/44'/461'/0/0/0", "", language_code).unwrap();
+    let xpub = extended_key.neuter().unwrap();
+
+    assert_eq!(xpub.public_key().to_vec(), extended_key.public_key.to_vec());
+    assert_eq!(Some(xpub.chain_code()), extended_key.chain_code());
+    assert!(xpub.derive_child(1).is_ok());
+
+    let recovered_key = key_recover(&extended_key.private_key, false).unwrap();
+    assert!(matches!(
+        recovered_key.neuter(),
+        Err(SignerError::MissingChainCode)
+    ));
+}
+
+#[test]
+fn derive_addresses_from_xpub() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let mnemonic = test_value["mnemonic"].as_str().unwrap();
+    let language_code = test_value["language_code"].as_str().unwrap();
+
+    let xpub = key_derive_xpub(mnemonic, "m/44'/461'/0/0", "", language_code).unwrap();
+
+    for index in 0..5 {
+        let child = xpub.derive_child(index).unwrap();
+        let address = Address::new_secp256k1(&child.public_key()).unwrap();
+
+        let extended_key = key_derive(
+            mnemonic,
+            &format!("m/44'/461'/0/0/{}", index),
+            "",
+            language_code,
+        )
+        .unwrap();
+
+        assert_eq!(address.to_string(), extended_key.address);
+        assert_eq!(Some(child.chain_code()), extended_key.chain_code());
+    }
+
+    assert!(xpub.derive_child(0x8000_0000).is_err());
+}
+
+#[test]
+fn derive_key_bls() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let mnemonic = test_value["mnemonic"].as_str().unwrap();
+    let language_code = test_value["language_code"].as_str().unwrap();
+
+    let secp_key = key_derive(mnemonic, "m/44'/461'/0/0/0", "", language_code).unwrap();
+    let bls_key = key_derive_bls(mnemonic, "m/44'/461'/0/0/0", "", language_code).unwrap();
+
+    assert_ne!(secp_key.address, bls_key.address);
+    assert!(bls_key.address.starts_with("f3"));
+    assert!(matches!(bls_key.public_key, PublicKey::BLSPublicKey(_)));
+
+    let recovered_key = key_recover_bls(&bls_key.private_key, false).unwrap();
+    assert_eq!(recovered_key.address, bls_key.address);
+
+    let bls_testnet_key = key_derive_bls(mnemonic, "m/44'/1'/0/0/0", "", language_code).unwrap();
+    assert!(bls_testnet_key.address.starts_with("t3"));
+}
+
+#[test]
+fn derive_key_accounts() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let mnemonic = test_value["mnemonic"].as_str().unwrap();
+    let language_code = test_value["language_code"].as_str().unwrap();
+
+    let accounts = key_derive_accounts(mnemonic, "m/44'/461'/0/0", "", language_code, 3).unwrap();
+
+    assert_eq!(accounts.len(), 3);
+    for (index, account) in accounts.iter().enumerate() {
+        let path = format!("m/44'/461'/0/0/{}", index);
+        let expected_key = key_derive(mnemonic, &path, "", language_code).unwrap();
+
+        assert!(account == &expected_key);
+        assert!(account.private_key == expected_key.private_key);
+    }
+    assert_eq!(
+        accounts[0].address,
+        "f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba"
+    );
+
+    let testnet_accounts =
+        key_derive_accounts(mnemonic, "m/44'/1'/0/0", "", language_code, 1).unwrap();
+    assert_eq!(
+        testnet_accounts[0].address,
+        "t137sjdbgunloi7couiy4l5nc7pd6k2jmq32vizpy"
+    );
+}
+
+#[test]
+fn generate_mnemonic_with_length() {
+    for words in [12, 15, 18, 21, 24] {
+        let mnemonic = key_generate_mnemonic_with_length(words).unwrap();
+
+        assert_eq!(mnemonic.0.split_whitespace().count(), words);
+        assert!(key_validate_mnemonic(&mnemonic.0, "en").is_ok());
+    }
+
+    assert!(matches!(
+        key_generate_mnemonic_with_length(13),
+        Err(SignerError::InvalidMnemonicWordCount(13))
+    ));
+}
+
+#[test]
+fn generate_mnemonic_lang() {
+    for language_code in ["ja", "zh-hans"] {
+        let mnemonic = key_generate_mnemonic_lang(12, language_code).unwrap();
+
+        assert_eq!(mnemonic.0.split_whitespace().count(), 12);
+        assert!(key_validate_mnemonic(&mnemonic.0, language_code).is_ok());
+        assert!(key_validate_mnemonic(&mnemonic.0, "en").is_err());
+
+        let extended_key = key_derive(&mnemonic.0, "m/44'/461'/0/0/0", "", language_code).unwrap();
+        assert!(extended_key.address.starts_with("f1"));
+    }
+
+    assert!(matches!(
+        key_generate_mnemonic_lang(12, "xx"),
+        Err(SignerError::UnknownLanguageCode(_))
+    ));
+}
+
+#[test]
+fn validate_mnemonic() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let mnemonic = test_value["mnemonic"].as_str().unwrap();
+
+    assert!(key_validate_mnemonic(mnemonic, "en").is_ok());
+
+    assert!(matches!(
+        key_validate_mnemonic(mnemonic, "xx"),
+        Err(SignerError::UnknownLanguageCode(_))
+    ));
+
+    let eleven_words =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    assert!(matches!(
+        key_validate_mnemonic(eleven_words, "en"),
+        Err(SignerError::InvalidMnemonicWordCount(11))
+    ));
+
+    let invalid_word = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandonx about";
+    assert!(matches!(
+        key_validate_mnemonic(invalid_word, "en"),
+        Err(SignerError::InvalidMnemonicWord)
+    ));
+
+    // "abandon ... abandon about" with the first and last words swapped
+    let transposed = "about abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+    assert!(matches!(
+        key_validate_mnemonic(transposed, "en"),
+        Err(SignerError::InvalidMnemonicChecksum)
+    ));
+
+    // Same errors when deriving a key
+    assert!(matches!(
+        key_derive(transposed, "m/44'/461'/0/0/0", "", "en"),
+        Err(SignerError::InvalidMnemonicChecksum)
+    ));
+    assert!(matches!(
+        key_derive(invalid_word, "m/44'/461'/0/0/0", "", "en"),
+        Err(SignerError::InvalidMnemonicWord)
+    ));
+}
+
+#[test]
+fn derive_key_all_networks() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let mnemonic = test_value["mnemonic"].as_str().unwrap();
+    let language_code = test_value["language_code"].as_str().unwrap();
+    let path = "m/44'/461'/0/0/0";
+
+    let (extended_key, testnet_address) =
+        key_derive_all_networks(mnemonic, path, "", language_code).unwrap();
+
+    let expected_key = key_derive(mnemonic, path, "", language_code).unwrap();
+
+    assert!(extended_key == expected_key);
+    assert_eq!(
+        extended_key.address,
+        "f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba"
+    );
+    assert_eq!(testnet_address, "t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba");
+}
+
+#[test]
+fn test_verify_mnemonic_address() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let mnemonic = test_value["mnemonic"].as_str().unwrap();
+    let language_code = test_value["language_code"].as_str().unwrap();
+    let child = test_value["childs"][3].to_owned();
+    let path = child["path"].as_str().unwrap();
+    let address = child["address"].as_str().unwrap();
+
+    assert!(verify_mnemonic_address(mnemonic, path, "", language_code, address).unwrap());
+    assert!(!verify_mnemonic_address(mnemonic, path, "password", language_code, address).unwrap());
+}
+
 #[test]
 fn test_key_recover_testnet() {
     let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
@@ -134,6 +342,205 @@ fn test_key_recover_mainnet() {
     assert_eq!(&recovered_key.address, &address);
 }
 
+#[test]
+fn test_keys_equality() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let private_key = test_value["private_key"].as_str().unwrap();
+
+    let pk = PrivateKey::try_from(private_key.to_string()).unwrap();
+    let same_pk = PrivateKey::try_from(private_key.to_string()).unwrap();
+    let mut other_pk = PrivateKey::try_from(private_key.to_string()).unwrap();
+    other_pk.0[31] ^= 0x01;
+
+    assert!(pk == same_pk);
+    assert!(pk != other_pk);
+
+    let testnet_key = key_recover(&pk, true).unwrap();
+    let same_testnet_key = key_recover(&same_pk, true).unwrap();
+    let mainnet_key = key_recover(&pk, false).unwrap();
+    let other_key = key_recover(&other_pk, true).unwrap();
+
+    assert!(testnet_key == same_testnet_key);
+    assert!(testnet_key != mainnet_key);
+    assert!(testnet_key != other_key);
+
+    let bls_test_value = common::load_test_vectors("../test_vectors/bls_wallet.json").unwrap();
+    let bls_private_key = bls_test_value["bls_private_key"].as_str().unwrap();
+
+    let bls_pk = PrivateKey::try_from(bls_private_key.to_string()).unwrap();
+    let bls_key = key_recover_bls(&bls_pk, true).unwrap();
+    let same_bls_key = key_recover_bls(&bls_pk, false).unwrap();
+
+    assert!(bls_key.public_key == same_bls_key.public_key);
+    assert!(bls_key.public_key != testnet_key.public_key);
+    assert!(bls_pk != pk);
+}
+
+#[test]
+fn test_public_key_compressed_round_trip() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let private_key = test_value["private_key"].as_str().unwrap();
+
+    let pk = PrivateKey::try_from(private_key.to_string()).unwrap();
+    let extended_key = key_recover(&pk, false).unwrap();
+
+    let compressed = extended_key.public_key.to_compressed().unwrap();
+    assert_eq!(compressed.0.len(), 33);
+    assert!(compressed.0[0] == 0x02 || compressed.0[0] == 0x03);
+
+    let parsed = PublicKeyCompressed::try_from(&compressed.0[..]).unwrap();
+    let public_key = parsed.to_public_key().unwrap();
+    assert!(public_key == extended_key.public_key);
+    assert_eq!(public_key.to_vec().len(), 65);
+
+    assert!(matches!(
+        PublicKeyCompressed::try_from(&compressed.0[1..]),
+        Err(SignerError::InvalidKeyLength {
+            expected: 33,
+            got: 32
+        })
+    ));
+
+    let bls_test_value = common::load_test_vectors("../test_vectors/bls_wallet.json").unwrap();
+    let bls_private_key = bls_test_value["bls_private_key"].as_str().unwrap();
+    let bls_pk = PrivateKey::try_from(bls_private_key.to_string()).unwrap();
+    let bls_key = key_recover_bls(&bls_pk, false).unwrap();
+    assert!(bls_key.public_key.to_compressed().is_err());
+}
+
+#[test]
+fn extended_key_json_round_trip() {
+    let wallet = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let mnemonic = wallet["mnemonic"].as_str().unwrap();
+    let language_code = wallet["language_code"].as_str().unwrap();
+
+    let extended_key = key_derive(mnemonic, "m/44'/461'/0/0/0", "", language_code).unwrap();
+
+    let json = serde_json::to_value(&extended_key).unwrap();
+    assert_eq!(json["private_key"], wallet["private_key"]);
+    assert_eq!(json["address"], "f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba");
+    assert_eq!(json["public_key"].as_str().unwrap().len(), 130);
+
+    let decoded: ExtendedKey = serde_json::from_value(json).unwrap();
+    assert!(decoded == extended_key);
+    assert!(decoded.private_key == extended_key.private_key);
+    assert_eq!(decoded.chain_code(), extended_key.chain_code());
+
+    let bls_wallet = common::load_test_vectors("../test_vectors/bls_wallet.json").unwrap();
+    let bls_private_key =
+        PrivateKey::try_from(bls_wallet["bls_private_key"].as_str().unwrap().to_string()).unwrap();
+    let bls_key = key_recover_bls(&bls_private_key, true).unwrap();
+
+    let json = serde_json::to_string(&bls_key).unwrap();
+    let decoded: ExtendedKey = serde_json::from_str(&json).unwrap();
+    assert!(decoded == bls_key);
+    assert!(decoded.chain_code().is_none());
+
+    let mut json = serde_json::to_value(&extended_key).unwrap();
+    json["public_key"] = serde_json::Value::from("00ff");
+    assert!(serde_json::from_value::<ExtendedKey>(json).is_err());
+}
+
+#[test]
+fn recover_bls_key_from_path() {
+    let bls_wallet = common::load_test_vectors("../test_vectors/bls_wallet.json").unwrap();
+    let private_key =
+        PrivateKey::try_from(bls_wallet["bls_private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let testnet_key = key_recover_bls_from_path(&private_key, "m/44'/1'/0/0/0").unwrap();
+    assert!(testnet_key.address.starts_with("t3"));
+    assert!(testnet_key == key_recover_bls(&private_key, true).unwrap());
+
+    let mainnet_key = key_recover_bls_from_path(&private_key, "m/44'/461'/0/0/0").unwrap();
+    assert!(mainnet_key.address.starts_with("f3"));
+
+    assert!(key_recover_bls_from_path(&private_key, "not a path").is_err());
+}
+
+#[test]
+fn lotus_key_export_import() {
+    // `lotus wallet export` output for the test wallet key
+    let lotus_export = "7b2254797065223a22736563703235366b31222c22507269766174654b6579223a2238566357303741447377533442563263786935726e4961645673795444446859314e66444831395438556f3d227d";
+
+    let key = import_key_lotus(lotus_export).unwrap();
+    assert_eq!(key.address, "f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba");
+    assert_eq!(export_key_lotus(&key).unwrap(), lotus_export);
+
+    let bls_wallet = common::load_test_vectors("../test_vectors/bls_wallet.json").unwrap();
+    let bls_private_key =
+        PrivateKey::try_from(bls_wallet["bls_private_key"].as_str().unwrap().to_string()).unwrap();
+    let bls_key = key_recover_bls(&bls_private_key, false).unwrap();
+
+    let bls_export = export_key_lotus(&bls_key).unwrap();
+    let json: serde_json::Value =
+        serde_json::from_slice(&hex::decode(&bls_export).unwrap()).unwrap();
+    assert_eq!(json["Type"], "bls");
+
+    let imported_bls_key = import_key_lotus(&bls_export).unwrap();
+    assert!(imported_bls_key == bls_key);
+
+    let unknown_type = hex::encode(
+        r#"{"Type":"delegated","PrivateKey":"8VcW07ADswS4BV2cxi5rnIadVsyTDDhY1NfDH19T8Uo="}"#,
+    );
+    assert!(matches!(
+        import_key_lotus(&unknown_type),
+        Err(SignerError::UnknownSigningProtocol)
+    ));
+}
+
+#[test]
+fn test_private_key_zeroize() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let private_key = test_value["private_key"].as_str().unwrap();
+
+    let mut pk = PrivateKey::try_from(private_key.to_string()).unwrap();
+    assert_ne!(pk.0, [0; 32]);
+
+    pk.zeroize();
+    assert_eq!(pk.0, [0; 32]);
+}
+
+#[test]
+fn test_key_from_test_seed() {
+    let key = key_from_test_seed(42, true).unwrap();
+    let same_key = key_from_test_seed(42, true).unwrap();
+    let other_key = key_from_test_seed(43, true).unwrap();
+
+    assert!(key.private_key == same_key.private_key);
+    assert!(key == same_key);
+    assert!(key.private_key != other_key.private_key);
+    assert!(key.address.starts_with("t1"));
+
+    let mainnet_key = key_from_test_seed(42, false).unwrap();
+    assert!(mainnet_key.address.starts_with("f1"));
+    assert_eq!(key.address[1..], mainnet_key.address[1..]);
+}
+
+#[test]
+fn test_key_info() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let private_key = test_value["private_key"].as_str().unwrap();
+    let bls_private_key = test_value["bls_private_key"].as_str().unwrap();
+    let bls_address = test_value["bls_address"].as_str().unwrap();
+
+    let pk = PrivateKey::try_from(private_key.to_string()).unwrap();
+    let info = key_info(&pk, SignatureType::Secp256k1).unwrap();
+
+    assert_eq!(info.sig_type, SignatureType::Secp256k1);
+    assert_eq!(info.address, "f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba");
+    assert_eq!(info.public_key.to_vec().len(), 65);
+
+    let bls_pk = PrivateKey::try_from(bls_private_key.to_string()).unwrap();
+    let bls_info = key_info(&bls_pk, SignatureType::BLS).unwrap();
+
+    assert_eq!(bls_info.sig_type, SignatureType::BLS);
+    assert_eq!(
+        utils::convert_address_network(&bls_info.address, Network::Testnet).unwrap(),
+        bls_address
+    );
+    assert_eq!(bls_info.public_key.to_vec().len(), 48);
+}
+
 #[test]
 fn parse_unsigned_transaction() {
     let test_value = common::load_test_vectors("../test_vectors/txs.json").unwrap();
@@ -169,6 +576,169 @@ fn parse_signed_transaction() {
     );
 }
 
+#[test]
+fn parse_signed_transaction_invalid_signature_length() {
+    // secp256k1 signature truncated to 64 bytes
+    let truncated = SIGNED_MESSAGE_CBOR.replace("58420106", "58410106");
+    let cbor_data = hex::decode(&truncated[..truncated.len() - 2]).unwrap();
+
+    assert!(matches!(
+        transaction_parse(&cbor_data, true),
+        Err(SignerError::InvalidSignatureLength(
+            SignatureType::Secp256k1,
+            65,
+            64
+        ))
+    ));
+
+    // 65 bytes signature declared as BLS
+    let cbor_data = hex::decode(SIGNED_MESSAGE_CBOR.replace("58420106", "58420206")).unwrap();
+
+    assert!(matches!(
+        transaction_parse(&cbor_data, true),
+        Err(SignerError::InvalidSignatureLength(
+            SignatureType::BLS,
+            96,
+            65
+        ))
+    ));
+}
+
+#[test]
+fn parse_unsigned_transaction_only() {
+    let test_value = common::load_test_vectors("../test_vectors/txs.json").unwrap();
+    let cbor_data = hex::decode(test_value[0]["cbor"].as_str().unwrap()).unwrap();
+    let to_expected =
+        Address::from_str(test_value[0]["transaction"]["To"].as_str().unwrap()).unwrap();
+
+    let message = transaction_parse_unsigned(&cbor_data, true).unwrap();
+    assert_eq!(message.to, to_expected);
+
+    let signed_cbor_data = hex::decode(SIGNED_MESSAGE_CBOR).unwrap();
+    assert!(matches!(
+        transaction_parse_unsigned(&signed_cbor_data, true),
+        Err(SignerError::UnexpectedSignedMessage)
+    ));
+}
+
+#[test]
+fn parse_transaction_invalid_gas_params() {
+    let message = Message {
+        version: 0,
+        to: Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+        from: Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap(),
+        sequence: 1,
+        value: TokenAmount::from_str("100000").unwrap(),
+        gas_limit: 25000,
+        gas_fee_cap: TokenAmount::from_str("2500").unwrap(),
+        gas_premium: TokenAmount::from_str("2501").unwrap(),
+        method_num: 0,
+        params: RawBytes::new(vec![]),
+    };
+
+    // Premium exceeding fee cap
+    let cbor = transaction_serialize(&message).unwrap();
+    assert!(matches!(
+        transaction_parse(&cbor, true),
+        Err(SignerError::InvalidGasParams(_))
+    ));
+
+    // Opting out of the check still accepts it
+    assert!(transaction_parse_unchecked(&cbor, true).is_ok());
+
+    // Negative premium
+    let negative_premium = Message {
+        gas_premium: TokenAmount::from_str("-1").unwrap(),
+        ..message.clone()
+    };
+    let cbor = transaction_serialize(&negative_premium).unwrap();
+    assert!(matches!(
+        transaction_parse(&cbor, true),
+        Err(SignerError::InvalidGasParams(_))
+    ));
+    assert!(transaction_parse_unchecked(&cbor, true).is_ok());
+
+    // Consistent gas values
+    let valid = Message {
+        gas_premium: TokenAmount::from_str("2500").unwrap(),
+        ..message
+    };
+    let cbor = transaction_serialize(&valid).unwrap();
+    assert!(transaction_parse(&cbor, true).is_ok());
+}
+
+#[test]
+fn transaction_json_round_trip() {
+    let test_value = common::load_test_vectors("../test_vectors/txs.json").unwrap();
+    let wallet = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let pk = PrivateKey::try_from(wallet["private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let message_api: MessageTxAPI =
+        serde_json::from_value(test_value[0]["transaction"].to_owned()).unwrap();
+    let message = message_api.get_message();
+
+    // Lotus also gives the version and the CID
+    let mut expected = test_value[0]["transaction"].to_owned();
+    expected["Version"] = 0.into();
+    expected["CID"] = serde_json::json!({ "/": message.cid().unwrap().to_string() });
+
+    // Unsigned message
+    let json = transaction_to_json(&message.marshal_cbor().unwrap(), true).unwrap();
+    let json_value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(json_value, expected);
+
+    // Signed message
+    let signed_message = transaction_sign(&message, &pk).unwrap();
+    let json = transaction_to_json(&signed_message.marshal_cbor().unwrap(), true).unwrap();
+    let json_value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(json_value["Message"], expected);
+    assert_eq!(json_value["Signature"]["Type"], 1);
+    assert_eq!(
+        json_value["CID"]["/"],
+        signed_message.cid().unwrap().to_string()
+    );
+
+    let parsed: MessageTxAPI = serde_json::from_str(&json).unwrap();
+    match parsed {
+        MessageTxAPI::SignedMessage(parsed) => {
+            assert_eq!(parsed.message, message);
+            assert_eq!(parsed.signature, signed_message.signature);
+        }
+        MessageTxAPI::Message(_) => panic!("Should be a Signed Message!"),
+    }
+}
+
+#[test]
+fn parse_concatenated_transactions() {
+    let test_value = common::load_test_vectors("../test_vectors/txs.json").unwrap();
+    let unsigned_cbor = hex::decode(test_value[0]["cbor"].as_str().unwrap()).unwrap();
+    let signed_cbor = hex::decode(SIGNED_MESSAGE_CBOR).unwrap();
+
+    let mut buffer = unsigned_cbor.clone();
+    buffer.extend_from_slice(&signed_cbor);
+
+    let (first_tx, consumed) = transaction_parse_prefix(&buffer, true).unwrap();
+    assert_eq!(consumed, unsigned_cbor.len());
+    assert!(matches!(first_tx, MessageTxAPI::Message(_)));
+
+    let (second_tx, consumed) = transaction_parse_prefix(&buffer[consumed..], true).unwrap();
+    assert_eq!(consumed, signed_cbor.len());
+    assert!(matches!(second_tx, MessageTxAPI::SignedMessage(_)));
+
+    // Trailing bytes are rejected by transaction_parse
+    assert!(transaction_parse(&buffer, true).is_err());
+
+    // Truncated input
+    assert!(matches!(
+        transaction_parse_prefix(&unsigned_cbor[..unsigned_cbor.len() - 3], true),
+        Err(SignerError::IncompleteCbor)
+    ));
+    assert!(matches!(
+        transaction_parse_prefix(&[], true),
+        Err(SignerError::IncompleteCbor)
+    ));
+}
+
 #[test]
 fn parse_transaction_with_network() {
     let test_value = common::load_test_vectors("../test_vectors/txs.json").unwrap();
@@ -253,6 +823,20 @@ fn parse_transaction_signed_with_network_testnet() {
     );
 }
 
+#[test]
+fn test_gas_estimate_payload() {
+    let test_value = common::load_test_vectors("../test_vectors/txs.json").unwrap();
+    let message_api: MessageTxAPI =
+        serde_json::from_value(test_value[0]["transaction"].to_owned()).unwrap();
+
+    let payload = gas_estimate_payload(&message_api.get_message()).unwrap();
+
+    assert_eq!(
+        payload,
+        r#"{"From":"t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba","To":"t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy","Nonce":1,"Value":"100000","Method":0,"Params":"","GasLimit":0,"GasFeeCap":"0","GasPremium":"0"}"#
+    );
+}
+
 #[test]
 fn verify_invalid_signature() {
     let test_value = common::load_test_vectors("../test_vectors/verify_signature.json").unwrap();
@@ -287,40 +871,392 @@ fn verify_invalid_signature() {
 }
 
 #[test]
-fn sign_bls_transaction() {
-    let test_value = common::load_test_vectors("../test_vectors/bls_wallet.json").unwrap();
-
-    // Get address
-    let bls_pubkey = hex::decode(test_value["bls_public_key"].as_str().unwrap()).unwrap();
-    let bls_address = Address::new_bls(bls_pubkey.as_slice()).unwrap();
+fn verify_signature_on_any_network() {
+    let test_value = common::load_test_vectors("../test_vectors/verify_signature.json").unwrap();
+    let private_key = test_value["verify_invalid_signature"]["private_key"]
+        .as_str()
+        .unwrap();
+    let message = test_value["verify_invalid_signature"]["message"].to_owned();
 
-    // Get BLS private key
-    let bls_key =
-        PrivateKey::try_from(test_value["bls_private_key"].as_str().unwrap().to_string()).unwrap();
+    let pk = PrivateKey::try_from(private_key.to_string()).unwrap();
+    let message_user_api: MessageTxAPI =
+        serde_json::from_value(message).expect("Could not serialize unsigned message");
+    let message_cbor = message_user_api.get_message().marshal_cbor().unwrap();
 
-    dbg!(bls_address.to_string());
+    let signature = transaction_sign_raw(&message_user_api.get_message(), &pk).unwrap();
 
-    // Prepare message with BLS address
-    let message = Message {
-        version: 0,
-        to: Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
-        from: bls_address,
-        sequence: 1,
-        value: TokenAmount::from_str("100000").unwrap(),
-        gas_limit: 25000,
-        gas_fee_cap: TokenAmount::from_str("2500").unwrap(),
-        gas_premium: TokenAmount::from_str("2500").unwrap(),
-        method_num: 0,
-        params: RawBytes::new(vec![]),
-    };
+    assert_eq!(
+        verify_signature_any_network(
+            &signature,
+            &message_cbor,
+            "t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba"
+        )
+        .unwrap(),
+        Some(Network::Testnet)
+    );
+    assert_eq!(
+        verify_signature_any_network(
+            &signature,
+            &message_cbor,
+            "f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba"
+        )
+        .unwrap(),
+        Some(Network::Mainnet)
+    );
 
-    let raw_sig = transaction_sign_raw(&message, &bls_key).unwrap();
+    // Valid signature, but not from the given signer
+    assert_eq!(
+        verify_signature_any_network(
+            &signature,
+            &message_cbor,
+            "t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy"
+        )
+        .unwrap(),
+        None
+    );
 
-    dbg!(hex::encode(raw_sig.bytes()));
+    // Signed by another key
+    let other_key = key_from_test_seed(1, true).unwrap();
+    let signature =
+        transaction_sign_raw(&message_user_api.get_message(), &other_key.private_key).unwrap();
 
-    let sig = bls_signatures::Signature::from_bytes(raw_sig.bytes()).expect("FIX ME");
+    assert_eq!(
+        verify_signature_any_network(&signature, &message_cbor, &other_key.address).unwrap(),
+        None
+    );
+}
 
-    let bls_pk = bls_signatures::PublicKey::from_bytes(&bls_pubkey).unwrap();
+#[test]
+fn verify_signature_without_recovery_byte() {
+    let test_value = common::load_test_vectors("../test_vectors/verify_signature.json").unwrap();
+    let private_key = test_value["verify_invalid_signature"]["private_key"]
+        .as_str()
+        .unwrap();
+    let message = test_value["verify_invalid_signature"]["message"].to_owned();
+
+    let pk = PrivateKey::try_from(private_key.to_string()).unwrap();
+    let message_user_api: MessageTxAPI =
+        serde_json::from_value(message).expect("Could not serialize unsigned message");
+    let message = message_user_api.get_message();
+
+    let signature = transaction_sign_raw(&message, &pk).unwrap();
+    let digest = utils::get_digest(&message.marshal_cbor().unwrap()).unwrap();
+
+    let mut rs = [0u8; 64];
+    rs.copy_from_slice(&signature.bytes[..64]);
+
+    assert!(verify_secp256k1_no_recovery(&digest, &rs, &message.from).unwrap());
+    assert!(!verify_secp256k1_no_recovery(&digest, &rs, &message.to).unwrap());
+}
+
+#[test]
+fn sign_with_keyring() {
+    let test_value = common::load_test_vectors("../test_vectors/verify_signature.json").unwrap();
+    let private_key = test_value["verify_invalid_signature"]["private_key"]
+        .as_str()
+        .unwrap();
+    let message = test_value["verify_invalid_signature"]["message"].to_owned();
+
+    let pk = PrivateKey::try_from(private_key.to_string()).unwrap();
+    let message_user_api: MessageTxAPI =
+        serde_json::from_value(message).expect("Could not serialize unsigned message");
+    let message = message_user_api.get_message();
+
+    let mut keyring = keyring::Keyring::new();
+    keyring.add(message.from, pk);
+
+    let signed_message = keyring.sign_by_address(&message).unwrap();
+    let message_cbor = message.marshal_cbor().unwrap();
+    assert!(verify_signature(&signed_message.signature, &message_cbor).unwrap());
+
+    let unknown_sender = Message {
+        from: message.to,
+        ..message
+    };
+    assert!(matches!(
+        keyring.sign_by_address(&unknown_sender),
+        Err(SignerError::KeyNotFound(_))
+    ));
+}
+
+#[test]
+fn sign_and_verify_message_with_version() {
+    let test_value = common::load_test_vectors("../test_vectors/verify_signature.json").unwrap();
+    let private_key = test_value["verify_invalid_signature"]["private_key"]
+        .as_str()
+        .unwrap();
+    let message = test_value["verify_invalid_signature"]["message"].to_owned();
+
+    let pk = PrivateKey::try_from(private_key.to_string()).unwrap();
+    let message_user_api: MessageTxAPI =
+        serde_json::from_value(message).expect("Could not serialize unsigned message");
+
+    let message_v0 = message_user_api.get_message();
+    let message_v1 = Message {
+        version: 1,
+        ..message_v0.clone()
+    };
+
+    assert_ne!(message_v0.to_signing_bytes(), message_v1.to_signing_bytes());
+
+    let signed_message = transaction_sign(&message_v1, &pk).unwrap();
+    assert_eq!(signed_message.message.version, 1);
+
+    let cbor_v0 = message_v0.marshal_cbor().unwrap();
+    let cbor_v1 = message_v1.marshal_cbor().unwrap();
+
+    assert!(verify_signature(&signed_message.signature, &cbor_v1).unwrap());
+    assert!(!verify_signature(&signed_message.signature, &cbor_v0).unwrap());
+}
+
+#[test]
+fn sign_and_verify_bls_message_with_version() {
+    let test_value = common::load_test_vectors("../test_vectors/bls_wallet.json").unwrap();
+
+    let bls_pubkey = hex::decode(test_value["bls_public_key"].as_str().unwrap()).unwrap();
+    let bls_address = Address::new_bls(bls_pubkey.as_slice()).unwrap();
+    let bls_key =
+        PrivateKey::try_from(test_value["bls_private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let message_v0 = Message {
+        version: 0,
+        to: Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+        from: bls_address,
+        sequence: 1,
+        value: TokenAmount::from_str("100000").unwrap(),
+        gas_limit: 25000,
+        gas_fee_cap: TokenAmount::from_str("2500").unwrap(),
+        gas_premium: TokenAmount::from_str("2500").unwrap(),
+        method_num: 0,
+        params: RawBytes::new(vec![]),
+    };
+    let message_v1 = Message {
+        version: 1,
+        ..message_v0.clone()
+    };
+
+    let signature = transaction_sign_raw(&message_v1, &bls_key).unwrap();
+
+    let cbor_v0 = message_v0.marshal_cbor().unwrap();
+    let cbor_v1 = message_v1.marshal_cbor().unwrap();
+
+    assert!(verify_signature(&signature, &cbor_v1).unwrap());
+    assert!(!verify_signature(&signature, &cbor_v0).unwrap());
+}
+
+#[test]
+fn sign_transaction_with_extra_entropy() {
+    let test_value = common::load_test_vectors("../test_vectors/verify_signature.json").unwrap();
+    let private_key = test_value["verify_invalid_signature"]["private_key"]
+        .as_str()
+        .unwrap();
+    let message = test_value["verify_invalid_signature"]["message"].to_owned();
+
+    let pk = PrivateKey::try_from(private_key.to_string()).unwrap();
+    let message_user_api: MessageTxAPI =
+        serde_json::from_value(message).expect("Could not serialize unsigned message");
+    let message = message_user_api.get_message();
+    let message_cbor = transaction_serialize(&message).unwrap();
+
+    let signature = transaction_sign_secp256k1_with_extra(&message, &pk, &[1u8; 32]).unwrap();
+
+    assert!(verify_signature(&signature, &message_cbor).unwrap());
+    assert_ne!(signature, transaction_sign_raw(&message, &pk).unwrap());
+    assert_eq!(
+        signature,
+        transaction_sign_secp256k1_with_extra(&message, &pk, &[1u8; 32]).unwrap()
+    );
+    assert_ne!(
+        signature,
+        transaction_sign_secp256k1_with_extra(&message, &pk, &[2u8; 32]).unwrap()
+    );
+}
+
+#[test]
+fn scan_addresses_gap_limit() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let mnemonic = test_value["mnemonic"].as_str().unwrap();
+
+    let mnemonic = bip39::Mnemonic::from_phrase(mnemonic, Language::English).unwrap();
+    let seed = Seed::new(&mnemonic, "");
+
+    let used_addresses = [
+        "f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba",
+        "f1rovwtiuo5ncslpmpjftzu5akswbgsgighjazxoi",
+    ];
+    let calls = std::cell::Cell::new(0);
+
+    let keys = scan_addresses(seed.as_bytes(), "m/44'/461'/0/0", 5, |address| {
+        calls.set(calls.get() + 1);
+        used_addresses.contains(&address)
+    })
+    .unwrap();
+
+    let addresses: Vec<&str> = keys.iter().map(|key| key.address.as_str()).collect();
+
+    assert_eq!(addresses, used_addresses);
+    assert_eq!(calls.get(), 7);
+}
+
+#[test]
+fn sign_transaction_unsupported_protocol() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let pk = PrivateKey::try_from(test_value["private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let message = Message {
+        version: 0,
+        to: Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+        from: Address::from_str("t01001").unwrap(),
+        sequence: 1,
+        value: TokenAmount::from_str("100000").unwrap(),
+        gas_limit: 25000,
+        gas_fee_cap: TokenAmount::from_str("2500").unwrap(),
+        gas_premium: TokenAmount::from_str("2500").unwrap(),
+        method_num: 0,
+        params: RawBytes::new(vec![]),
+    };
+
+    assert!(matches!(
+        transaction_sign_raw(&message, &pk),
+        Err(SignerError::UnknownSigningProtocol)
+    ));
+
+    // Delegated addresses are not supported by the address type yet
+    assert!(Address::from_str("f410fkkld55ioe7qg24wvt7fu6pbknb56ht7pt4zamxa").is_err());
+}
+
+#[test]
+fn test_sign_signing_bytes() {
+    let test_value = common::load_test_vectors("../test_vectors/verify_signature.json").unwrap();
+    let private_key = test_value["verify_invalid_signature"]["private_key"]
+        .as_str()
+        .unwrap();
+    let message = test_value["verify_invalid_signature"]["message"].to_owned();
+
+    let pk = PrivateKey::try_from(private_key.to_string()).unwrap();
+    let message_user_api: MessageTxAPI =
+        serde_json::from_value(message).expect("Could not serialize unsigned message");
+    let message = message_user_api.get_message();
+
+    let signature =
+        sign_signing_bytes(&message.to_signing_bytes(), &pk, SignatureType::Secp256k1).unwrap();
+    let expected_signature = transaction_sign_raw(&message, &pk).unwrap();
+
+    assert_eq!(signature, expected_signature);
+
+    let bls_test_value = common::load_test_vectors("../test_vectors/bls_wallet.json").unwrap();
+    let bls_key = PrivateKey::try_from(
+        bls_test_value["bls_private_key"]
+            .as_str()
+            .unwrap()
+            .to_string(),
+    )
+    .unwrap();
+    let bls_pubkey = hex::decode(bls_test_value["bls_public_key"].as_str().unwrap()).unwrap();
+
+    let bls_message = Message {
+        from: Address::new_bls(bls_pubkey.as_slice()).unwrap(),
+        ..message
+    };
+
+    let signature = sign_signing_bytes(
+        &bls_message.to_signing_bytes(),
+        &bls_key,
+        SignatureType::BLS,
+    )
+    .unwrap();
+    let expected_signature = transaction_sign_raw(&bls_message, &bls_key).unwrap();
+
+    assert_eq!(signature, expected_signature);
+}
+
+#[test]
+fn message_version_round_trip() {
+    let test_value = common::load_test_vectors("../test_vectors/verify_signature.json").unwrap();
+    let message = test_value["verify_invalid_signature"]["message"].to_owned();
+
+    let message_user_api: MessageTxAPI =
+        serde_json::from_value(message).expect("Could not serialize unsigned message");
+    let message_v1 = Message {
+        version: 1,
+        ..message_user_api.get_message()
+    };
+
+    // JSON
+    let json = serde_json::to_value(MessageTxAPI::Message(message_v1.clone())).unwrap();
+    assert_eq!(json["Version"], 1);
+
+    let parsed: MessageTxAPI = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed.get_message().version, 1);
+    assert_eq!(
+        parsed.get_message().marshal_cbor().unwrap(),
+        message_v1.marshal_cbor().unwrap()
+    );
+
+    // Version 0 is left out of the JSON
+    let json = serde_json::to_value(&message_user_api).unwrap();
+    assert!(json.get("Version").is_none());
+
+    // CBOR
+    let cbor = message_v1.marshal_cbor().unwrap();
+    assert_eq!(
+        transaction_parse(&cbor, true)
+            .unwrap()
+            .get_message()
+            .version,
+        1
+    );
+
+    // Messages completed after gas estimation
+    let partial: api::PartialMessage = serde_json::from_value(serde_json::json!({
+        "Version": 1,
+        "From": "t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba",
+        "To": "t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy",
+        "Nonce": 1,
+        "Value": "100000",
+        "Method": 0,
+        "Params": "",
+    }))
+    .unwrap();
+    let message = partial.fill_gas(25000, TokenAmount::from(2500), TokenAmount::from(2500));
+    assert_eq!(message.version, 1);
+}
+
+#[test]
+fn sign_bls_transaction() {
+    let test_value = common::load_test_vectors("../test_vectors/bls_wallet.json").unwrap();
+
+    // Get address
+    let bls_pubkey = hex::decode(test_value["bls_public_key"].as_str().unwrap()).unwrap();
+    let bls_address = Address::new_bls(bls_pubkey.as_slice()).unwrap();
+
+    // Get BLS private key
+    let bls_key =
+        PrivateKey::try_from(test_value["bls_private_key"].as_str().unwrap().to_string()).unwrap();
+
+    dbg!(bls_address.to_string());
+
+    // Prepare message with BLS address
+    let message = Message {
+        version: 0,
+        to: Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+        from: bls_address,
+        sequence: 1,
+        value: TokenAmount::from_str("100000").unwrap(),
+        gas_limit: 25000,
+        gas_fee_cap: TokenAmount::from_str("2500").unwrap(),
+        gas_premium: TokenAmount::from_str("2500").unwrap(),
+        method_num: 0,
+        params: RawBytes::new(vec![]),
+    };
+
+    let raw_sig = transaction_sign_raw(&message, &bls_key).unwrap();
+
+    dbg!(hex::encode(raw_sig.bytes()));
+
+    let sig = bls_signatures::Signature::from_bytes(raw_sig.bytes()).expect("FIX ME");
+
+    let bls_pk = bls_signatures::PublicKey::from_bytes(&bls_pubkey).unwrap();
 
     let message_cbor = message.marshal_cbor().expect("FIX ME");
 
@@ -398,6 +1334,79 @@ fn test_verify_aggregated_signature() {
     let sig = Signature::new_bls(aggregated_signature.as_bytes());
 
     assert!(verify_aggregated_signature(&sig, &cbor_messages[..]).unwrap());
+
+    // verify the same signature with messages streamed one at a time
+    let mut verifier = AggregateVerifier::new();
+    for cbor_message in cbor_messages.iter() {
+        verifier.push(cbor_message).unwrap();
+    }
+
+    assert!(verifier.finish(&sig).unwrap());
+
+    let mut verifier = AggregateVerifier::new();
+    verifier.push(&cbor_messages[0]).unwrap();
+
+    assert!(!verifier.finish(&sig).unwrap());
+}
+
+#[test]
+fn test_sign_bls_aggregate() {
+    let fixture = common::bls_aggregate_fixture(7, 3);
+
+    let private_keys: Vec<PrivateKey> = fixture
+        .private_keys
+        .iter()
+        .map(|private_key| PrivateKey::try_from(private_key.as_bytes()).unwrap())
+        .collect();
+    let messages = fixture.messages;
+
+    let sig = transaction_sign_bls_aggregate(&messages, &private_keys).unwrap();
+
+    assert_eq!(sig, fixture.signature);
+    assert!(verify_aggregated_signature(&sig, &fixture.cbor_messages).unwrap());
+
+    // Slices of different length
+    assert!(matches!(
+        transaction_sign_bls_aggregate(&messages[..2], &private_keys),
+        Err(SignerError::LengthMismatch {
+            messages: 2,
+            keys: 3
+        })
+    ));
+
+    // Sender is not a BLS address
+    let secp_message = Message {
+        from: Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap(),
+        ..messages[0].clone()
+    };
+    assert!(matches!(
+        transaction_sign_bls_aggregate(&[secp_message], &private_keys[..1]),
+        Err(SignerError::UnknownSigningProtocol)
+    ));
+}
+
+#[test]
+fn test_aggregated_signature_canonical_order() {
+    let fixture = common::bls_aggregate_fixture(42, 3);
+    let sig = fixture.signature;
+
+    let mut ordered: Vec<Message> = fixture.messages.clone();
+    let mut reversed: Vec<Message> = ordered.iter().rev().cloned().collect();
+
+    utils::sort_messages_for_aggregation(&mut ordered);
+    utils::sort_messages_for_aggregation(&mut reversed);
+
+    assert_eq!(ordered, reversed);
+
+    // The aggregated signature verifies whatever the order of the messages
+    for messages in [&fixture.messages, &ordered, &reversed] {
+        let cbor_messages: Vec<Vec<u8>> = messages
+            .iter()
+            .map(|message| transaction_serialize(message).unwrap())
+            .collect();
+
+        assert!(verify_aggregated_signature(&sig, &cbor_messages).unwrap());
+    }
 }
 
 #[test]
@@ -464,28 +1473,273 @@ fn test_sign_voucher() {
 }
 
 #[test]
-fn test_verify_voucher_signature() {
-    let test_value = common::load_test_vectors("../test_vectors/voucher.json").unwrap();
+fn test_sign_voucher_with_secret_and_extra() {
+    let wallet = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let mnemonic = wallet["mnemonic"].as_str().unwrap();
+    let language_code = wallet["language_code"].as_str().unwrap();
 
-    let voucher_base64_string = test_value["verify"]["signed_voucher_base64"]
-        .as_str()
-        .unwrap()
-        .to_string();
-    let address_signer = test_value["verify"]["address_signer"]
-        .as_str()
-        .unwrap()
-        .to_string();
+    let extended_key = key_derive(mnemonic, "m/44'/461'/0/0/0", "", language_code).unwrap();
 
-    let result = verify_voucher_signature(voucher_base64_string, address_signer).expect("FIX ME");
+    let extra = fil_actor_paych::ModVerifyParams {
+        actor: Address::from_str("f01001").unwrap(),
+        method: 2,
+        data: RawBytes::new(vec![0xde, 0xad]),
+    };
 
-    assert!(result);
+    let voucher = create_voucher_full(
+        "f24acjqhdetck7irsvmn2p6jpuwnouzjxuoa22rva".to_string(),
+        0,
+        0,
+        "100000".to_string(),
+        1,
+        1,
+        0,
+        b"secret".to_vec(),
+        Some(extra.clone()),
+    )
+    .unwrap();
+
+    let signed_voucher = sign_voucher(voucher, &extended_key.private_key).unwrap();
+
+    assert!(
+        verify_voucher_signature(signed_voucher.clone(), extended_key.address.clone()).unwrap()
+    );
+
+    let mut decoded: fil_actor_paych::SignedVoucher =
+        fvm_ipld_encoding::from_slice(&base64::decode(&signed_voucher).unwrap()).unwrap();
+    assert_eq!(decoded.secret_pre_image, b"secret".to_vec());
+    assert_eq!(decoded.extra, Some(extra));
+
+    // The preimage is part of the signing bytes
+    decoded.secret_pre_image = b"other".to_vec();
+    let tampered_voucher = base64::encode(to_vec(&decoded).unwrap());
+    assert!(matches!(
+        verify_voucher_signature(tampered_voucher, extended_key.address),
+        Err(SignerError::AddressMismatch)
+    ));
 }
 
 #[test]
-fn test_get_cid() {
-    let test_value = common::load_test_vectors("../test_vectors/get_cid.json").unwrap();
+fn test_sign_voucher_bls() {
+    let test_value = common::load_test_vectors("../test_vectors/bls_wallet.json").unwrap();
+    let private_key =
+        PrivateKey::try_from(test_value["bls_private_key"].as_str().unwrap().to_string()).unwrap();
+    let bls_key = key_recover_bls(&private_key, true).unwrap();
 
-    let expected_cid = test_value["cid"].as_str().unwrap().to_string();
+    let voucher = create_voucher(
+        "t24acjqhdetck7irsvmn2p6jpuwnouzjxuoa22rva".to_string(),
+        0,
+        0,
+        "100000".to_string(),
+        1,
+        1,
+        0,
+    )
+    .unwrap();
+
+    let signed_voucher = sign_voucher_bls(voucher, &bls_key.private_key).unwrap();
+
+    let decoded: fil_actor_paych::SignedVoucher =
+        fvm_ipld_encoding::from_slice(&base64::decode(&signed_voucher).unwrap()).unwrap();
+    assert_eq!(decoded.signature.unwrap().sig_type, SignatureType::BLS);
+
+    assert!(verify_voucher_signature(signed_voucher, bls_key.address).unwrap());
+}
+
+#[test]
+fn test_recover_voucher_signer() {
+    let wallet = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let private_key =
+        PrivateKey::try_from(wallet["private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let voucher = create_voucher(
+        "t24acjqhdetck7irsvmn2p6jpuwnouzjxuoa22rva".to_string(),
+        0,
+        0,
+        "100000".to_string(),
+        1,
+        1,
+        0,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        recover_voucher_signer(voucher.clone(), Network::Testnet),
+        Err(SignerError::VoucherNotSigned)
+    ));
+
+    let signed_voucher = sign_voucher(voucher.clone(), &private_key).unwrap();
+
+    assert_eq!(
+        recover_voucher_signer(signed_voucher.clone(), Network::Testnet).unwrap(),
+        "t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba"
+    );
+    assert_eq!(
+        recover_voucher_signer(signed_voucher, Network::Mainnet).unwrap(),
+        "f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba"
+    );
+
+    let bls_wallet = common::load_test_vectors("../test_vectors/bls_wallet.json").unwrap();
+    let bls_private_key =
+        PrivateKey::try_from(bls_wallet["bls_private_key"].as_str().unwrap().to_string()).unwrap();
+    let bls_signed_voucher = sign_voucher_bls(voucher, &bls_private_key).unwrap();
+
+    assert!(matches!(
+        recover_voucher_signer(bls_signed_voucher, Network::Testnet),
+        Err(SignerError::UnknownSigningProtocol)
+    ));
+}
+
+#[test]
+fn test_compute_paych_address() {
+    // Payment channel of the voucher test vectors, created by this account with nonce 3
+    let creator = Address::from_str("t137sjdbgunloi7couiy4l5nc7pd6k2jmq32vizpy").unwrap();
+
+    let paych_address = compute_paych_address(&creator, 3).unwrap();
+    assert_eq!(
+        paych_address.to_string(),
+        "t24acjqhdetck7irsvmn2p6jpuwnouzjxuoa22rva"
+    );
+
+    let other_address = compute_paych_address(&creator, 4).unwrap();
+    assert_ne!(other_address, paych_address);
+}
+
+#[test]
+fn test_verify_voucher_signature() {
+    let test_value = common::load_test_vectors("../test_vectors/voucher.json").unwrap();
+
+    let voucher_base64_string = test_value["verify"]["signed_voucher_base64"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let address_signer = test_value["verify"]["address_signer"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let result = verify_voucher_signature(voucher_base64_string, address_signer).expect("FIX ME");
+
+    assert!(result);
+}
+
+#[test]
+fn verify_voucher_signature_signer_comparison() {
+    let test_value = common::load_test_vectors("../test_vectors/voucher.json").unwrap();
+    let voucher_base64_string = test_value["verify"]["signed_voucher_base64"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Same signer on both networks
+    for signer in [
+        "t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba",
+        "f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba",
+    ] {
+        assert!(
+            verify_voucher_signature(voucher_base64_string.clone(), signer.to_string()).unwrap()
+        );
+    }
+
+    // Another secp256k1 signer
+    assert!(matches!(
+        verify_voucher_signature(
+            voucher_base64_string,
+            "f17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy".to_string()
+        ),
+        Err(SignerError::AddressMismatch)
+    ));
+}
+
+#[test]
+fn test_structured_errors() {
+    // Voucher without signature
+    let voucher = create_voucher(
+        "t24acjqhdetck7irsvmn2p6jpuwnouzjxuoa22rva".to_string(),
+        0,
+        0,
+        "10000".to_string(),
+        1,
+        1,
+        0,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        verify_voucher_signature(
+            voucher,
+            "t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba".to_string()
+        ),
+        Err(SignerError::VoucherNotSigned)
+    ));
+
+    // Voucher signed by someone else
+    let test_value = common::load_test_vectors("../test_vectors/voucher.json").unwrap();
+    let voucher_base64_string = test_value["verify"]["signed_voucher_base64"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    assert!(matches!(
+        verify_voucher_signature(
+            voucher_base64_string,
+            "t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy".to_string()
+        ),
+        Err(SignerError::AddressMismatch)
+    ));
+
+    assert!(matches!(
+        deserialize_params_str("".to_string(), "unknown".to_string(), 2),
+        Err(SignerError::UnsupportedActorType(actor_type)) if actor_type == "unknown"
+    ));
+
+    assert!(matches!(
+        PrivateKey::try_from(vec![1u8; 16]),
+        Err(SignerError::InvalidKeyLength {
+            expected: 32,
+            got: 16
+        })
+    ));
+}
+
+#[test]
+fn test_voucher_settle_ready() {
+    let voucher = create_voucher(
+        "t24acjqhdetck7irsvmn2p6jpuwnouzjxuoa22rva".to_string(),
+        10,
+        100,
+        "10000".to_string(),
+        1,
+        1,
+        50,
+    )
+    .unwrap();
+
+    assert!(!voucher_settle_ready(voucher.clone(), 5).unwrap());
+    assert!(!voucher_settle_ready(voucher.clone(), 20).unwrap());
+    assert!(voucher_settle_ready(voucher.clone(), 50).unwrap());
+    assert!(voucher_settle_ready(voucher.clone(), 100).unwrap());
+    assert!(!voucher_settle_ready(voucher, 101).unwrap());
+
+    let voucher_no_max = create_voucher(
+        "t24acjqhdetck7irsvmn2p6jpuwnouzjxuoa22rva".to_string(),
+        0,
+        0,
+        "10000".to_string(),
+        1,
+        1,
+        0,
+    )
+    .unwrap();
+
+    assert!(voucher_settle_ready(voucher_no_max, 1_000_000).unwrap());
+}
+
+#[test]
+fn test_get_cid() {
+    let test_value = common::load_test_vectors("../test_vectors/get_cid.json").unwrap();
+
+    let expected_cid = test_value["cid"].as_str().unwrap().to_string();
     let message_api: MessageTxAPI = serde_json::from_value(test_value["signed_message"].to_owned())
         .expect("couldn't serialize signed message");
 
@@ -494,6 +1748,69 @@ fn test_get_cid() {
     assert_eq!(cid, expected_cid);
 }
 
+#[test]
+fn test_get_cid_raw() {
+    let test_value = common::load_test_vectors("../test_vectors/get_cid.json").unwrap();
+
+    let expected_cid = test_value["cid"].as_str().unwrap().to_string();
+    let message_api: MessageTxAPI = serde_json::from_value(test_value["signed_message"].to_owned())
+        .expect("couldn't serialize signed message");
+
+    let cid = get_cid_raw(&message_api).unwrap();
+    let cid_bytes = get_cid_bytes(&message_api).unwrap();
+
+    assert_eq!(cid.to_string(), expected_cid);
+    assert_eq!(get_cid(message_api).unwrap(), cid.to_string());
+    assert_eq!(cid_bytes, cid.to_bytes());
+}
+
+#[test]
+fn test_verify_message_cid() {
+    let test_value = common::load_test_vectors("../test_vectors/get_cid.json").unwrap();
+
+    let cid = test_value["cid"].as_str().unwrap();
+    let message_api: MessageTxAPI = serde_json::from_value(test_value["signed_message"].to_owned())
+        .expect("couldn't serialize signed message");
+
+    let mut signed_message = match message_api {
+        MessageTxAPI::SignedMessage(signed_message) => signed_message,
+        MessageTxAPI::Message(_) => panic!("Should be a Signed Message!"),
+    };
+
+    assert!(verify_message_cid(&signed_message, cid).unwrap());
+
+    signed_message.message.sequence += 1;
+    assert!(!verify_message_cid(&signed_message, cid).unwrap());
+
+    assert!(verify_message_cid(&signed_message, "not a cid").is_err());
+}
+
+#[test]
+fn test_cids_of_messages() {
+    let messages: Vec<Message> = (0..10)
+        .map(|i| Message {
+            version: 0,
+            to: Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+            from: Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap(),
+            sequence: i,
+            value: TokenAmount::from_str("100000").unwrap(),
+            gas_limit: 25000,
+            gas_fee_cap: TokenAmount::from_str("2500").unwrap(),
+            gas_premium: TokenAmount::from_str("2500").unwrap(),
+            method_num: 0,
+            params: RawBytes::new(vec![]),
+        })
+        .collect();
+
+    let cids = cids_of_messages(&messages).unwrap();
+
+    assert_eq!(cids.len(), messages.len());
+    for (cid, message) in cids.iter().zip(messages) {
+        let expected_cid = get_cid(MessageTxAPI::Message(message)).unwrap();
+        assert_eq!(cid.to_string(), expected_cid);
+    }
+}
+
 #[test]
 fn test_multisig_v1_deserialize() {
     let expected_params = multisig::ConstructorParams {
@@ -528,6 +1845,54 @@ fn test_multisig_v1_deserialize() {
     }
 }
 
+#[test]
+fn test_multisig_historical_deserialize() {
+    let signer_1 = Address::from_str("f125nlfn4lwl7ldt4gwfas5furnwafwqgd2our6oa").unwrap();
+    let signer_2 = Address::from_str("f17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap();
+
+    // v2 layout: signers, threshold, unlock duration and start epoch
+    let v2_params = "hIJVAddasreLsv6xz4axQS6WkW2AW0DDVQH9HQ9N/Nfpmvy5moMmt9xFnTLGKAIYZAo=";
+
+    for code_cid in [
+        "fil/2/multisig",
+        "fil/7/multisig",
+        // Legacy identity code CID of "fil/2/multisig"
+        "bafkqadtgnfwc6mrpnv2wy5djonuwo",
+    ] {
+        let params =
+            deserialize_constructor_params(v2_params.to_string(), code_cid.to_string()).unwrap();
+
+        match params {
+            MessageParams::MultisigConstructorParams(p) => {
+                assert_eq!(p.signers, vec![signer_1, signer_2]);
+                assert_eq!(p.num_approvals_threshold, 2);
+                assert_eq!(p.unlock_duration, 100);
+                assert_eq!(p.start_epoch, 10);
+            }
+            _ => panic!("Not matching"),
+        }
+    }
+
+    // v1 layout has no start epoch
+    let params = deserialize_constructor_params(
+        "g4FVAddasreLsv6xz4axQS6WkW2AW0DDAQA=".to_string(),
+        "fil/1/multisig".to_string(),
+    )
+    .unwrap();
+    match params {
+        MessageParams::MultisigConstructorParams(p) => {
+            assert_eq!(p.signers, vec![signer_1]);
+            assert_eq!(p.start_epoch, 0);
+        }
+        _ => panic!("Not matching"),
+    }
+
+    assert!(matches!(
+        deserialize_constructor_params(v2_params.to_string(), "fil/9/multisig".to_string()),
+        Err(SignerError::UnsupportedCodeCid(code_cid)) if code_cid == "fil/9/multisig"
+    ));
+}
+
 #[test]
 fn test_serialize() {
     let expected_params = multisig::ChangeNumApprovalsThresholdParams { new_threshold: 2 };
@@ -546,3 +1911,1260 @@ fn test_serialize() {
 
     assert_eq!(params_multisig.new_threshold, expected_params.new_threshold);
 }
+
+#[test]
+fn test_serialize_raw_params() {
+    let params = MessageParams::ChangeNumApprovalsThresholdParams(
+        multisig::ChangeNumApprovalsThresholdParams { new_threshold: 2 },
+    );
+    let expected = serialize_params(params).unwrap();
+
+    let value = serde_cbor::Value::Array(vec![serde_cbor::Value::Integer(2)]);
+    let raw_params = serialize_raw_params(&value).unwrap();
+
+    assert_eq!(raw_params, expected);
+}
+
+#[test]
+fn test_build_swap_signer_proposal() {
+    let multisig_address = Address::from_str("t01004").unwrap();
+    let from = Address::from_str("t01001").unwrap();
+    let old_signer = Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap();
+    let new_signer = Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap();
+
+    let (message, proposal_hash) =
+        api::build_swap_signer_proposal(multisig_address, from, old_signer, new_signer, 3).unwrap();
+
+    assert_eq!(message.to, multisig_address);
+    assert_eq!(message.from, from);
+    assert_eq!(message.sequence, 3);
+    assert_eq!(message.method_num, multisig::Method::Propose as u64);
+
+    let params = deserialize_params(
+        base64::encode(message.params.bytes()),
+        ActorType::Multisig,
+        message.method_num,
+    )
+    .unwrap();
+
+    let propose_params = match params {
+        MessageParams::ProposeParams(p) => p,
+        _ => panic!("Should be ProposeParams"),
+    };
+
+    // CBOR addresses don't carry a network
+    let swap_params: multisig::SwapSignerParams = propose_params.params.deserialize().unwrap();
+    assert_eq!(swap_params.from.payload(), old_signer.payload());
+    assert_eq!(swap_params.to.payload(), new_signer.payload());
+
+    let expected_hash = compute_proposal_hash(ProposalHashDataAPI {
+        requester: Some(from),
+        to: propose_params.to,
+        value: propose_params.value,
+        method: propose_params.method,
+        params: propose_params.params,
+    })
+    .unwrap();
+
+    assert_eq!(proposal_hash, expected_hash);
+}
+
+#[test]
+fn test_actor_methods() {
+    let methods = actor_methods("multisig").unwrap();
+
+    assert!(methods.contains(&(2, "Propose")));
+    assert!(methods.contains(&(7, "SwapSigner")));
+
+    let methods = actor_methods("paymentchannel").unwrap();
+
+    assert_eq!(methods.len(), 3);
+    assert!(methods.contains(&(2, "UpdateChannelState")));
+
+    assert_eq!(
+        actor_methods("datacap").unwrap(),
+        vec![(80_475_954, "Transfer")]
+    );
+    assert!(actor_methods("unknown").is_none());
+}
+
+#[test]
+fn actor_type_from_str() {
+    assert_eq!(
+        ActorType::from_str("multisig").unwrap(),
+        ActorType::Multisig
+    );
+    assert_eq!(
+        ActorType::from_str("paymentchannel").unwrap(),
+        ActorType::PaymentChannel
+    );
+    assert!(matches!(
+        ActorType::from_str("multisgi"),
+        Err(SignerError::UnsupportedActorType(actor_type)) if actor_type == "multisgi"
+    ));
+}
+
+#[test]
+fn deserialize_params_verifreg_add_verified_client() {
+    let params = fil_actor_verifreg::AddVerifierClientParams {
+        address: Address::from_str("f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap(),
+        allowance: TokenAmount::from(1_099_511_627_776u64),
+    };
+
+    let serialized = MessageParams::AddVerifiedClientParams(params.clone())
+        .serialize()
+        .unwrap();
+
+    let deserialized = deserialize_params(
+        base64::encode(serialized.bytes()),
+        ActorType::Verifreg,
+        fil_actor_verifreg::Method::AddVerifiedClient as u64,
+    )
+    .unwrap();
+
+    match deserialized {
+        MessageParams::AddVerifiedClientParams(p) => assert_eq!(p, params),
+        _ => panic!("Should be AddVerifiedClientParams"),
+    }
+}
+
+#[test]
+fn deserialize_params_datacap_transfer() {
+    let params = extras::datacap::TransferParams {
+        to: Address::from_str("f01004").unwrap(),
+        amount: TokenAmount::from(34_359_738_368u64),
+        operator_data: RawBytes::new(vec![0x80]),
+    };
+
+    let serialized = MessageParams::DatacapTransferParams(params.clone())
+        .serialize()
+        .unwrap();
+
+    let deserialized = deserialize_params(
+        base64::encode(serialized.bytes()),
+        ActorType::Datacap,
+        extras::datacap::method::TRANSFER,
+    )
+    .unwrap();
+
+    match deserialized {
+        MessageParams::DatacapTransferParams(p) => assert_eq!(p, params),
+        _ => panic!("Should be DatacapTransferParams"),
+    }
+
+    assert!(deserialize_params(base64::encode(serialized.bytes()), ActorType::Datacap, 2).is_err());
+    assert!(matches!(
+        deserialize_params_str("".to_string(), "account".to_string(), 2),
+        Err(SignerError::UnsupportedActorType(_))
+    ));
+}
+
+#[test]
+fn test_verify_proposal_hash() {
+    let proposal = || ProposalHashDataAPI {
+        requester: Some(Address::from_str("t01001").unwrap()),
+        to: Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+        value: TokenAmount::from_str("1000").unwrap(),
+        method: 0,
+        params: RawBytes::new(vec![]),
+    };
+
+    let proposal_hash = compute_proposal_hash(proposal()).unwrap();
+    assert!(verify_proposal_hash(proposal(), &proposal_hash).unwrap());
+
+    let mut tampered_hash = base64::decode(&proposal_hash).unwrap();
+    tampered_hash[0] ^= 0x01;
+    assert!(!verify_proposal_hash(proposal(), &base64::encode(&tampered_hash)).unwrap());
+
+    let other_proposal = ProposalHashDataAPI {
+        value: TokenAmount::from_str("1001").unwrap(),
+        ..proposal()
+    };
+    assert!(!verify_proposal_hash(other_proposal, &proposal_hash).unwrap());
+}
+
+#[test]
+fn deserialize_params_invoke_evm() {
+    // transfer(address,uint256) selector and arguments
+    let calldata = hex::decode(
+        "a9059cbb\
+         00000000000000000000000052963ef50e27e06d72d59fcb4f3c2a687be3cfef\
+         00000000000000000000000000000000000000000000000000000000000003e8",
+    )
+    .unwrap();
+
+    let serialized = MessageParams::InvokeEVM(calldata.clone())
+        .serialize()
+        .unwrap();
+
+    // CBOR byte string header (major type 2, 1 byte length) followed by the calldata
+    assert_eq!(serialized.bytes()[..2], [0x58, 68]);
+    assert_eq!(serialized.bytes()[2..], calldata[..]);
+
+    let deserialized = deserialize_params(
+        base64::encode(serialized.bytes()),
+        ActorType::Evm,
+        EVM_INVOKE_CONTRACT_METHOD,
+    )
+    .unwrap();
+
+    match deserialized {
+        MessageParams::InvokeEVM(p) => assert_eq!(p, calldata),
+        _ => panic!("Should be InvokeEVM"),
+    }
+}
+
+#[test]
+fn verify_many_signatures_batch() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let pk = PrivateKey::try_from(test_value["private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let test_txs = common::load_test_vectors("../test_vectors/txs.json").unwrap();
+    let valid_cbor = hex::decode(test_txs[0]["cbor"].as_str().unwrap()).unwrap();
+    let message = transaction_parse_unsigned(&valid_cbor, true).unwrap();
+    let signature = transaction_sign_raw(&message, &pk).unwrap();
+
+    let mut tampered_cbor = valid_cbor.clone();
+    let last = tampered_cbor.len() - 1;
+    tampered_cbor[last] ^= 0x01;
+
+    let truncated_signature = Signature::new_secp256k1(signature.bytes[..10].to_vec());
+
+    let items = vec![
+        (signature.clone(), valid_cbor.clone()),
+        (signature.clone(), tampered_cbor),
+        (signature.clone(), vec![0x00, 0x01]),
+        (truncated_signature, valid_cbor.clone()),
+        (signature, valid_cbor),
+    ];
+
+    let results = verify_signatures_batch(&items).unwrap();
+
+    assert_eq!(results, vec![true, false, false, false, true]);
+}
+
+#[test]
+fn sign_transaction_with_closure() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let pk = PrivateKey::try_from(test_value["private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let test_txs = common::load_test_vectors("../test_vectors/txs.json").unwrap();
+    let message_cbor = hex::decode(test_txs[0]["cbor"].as_str().unwrap()).unwrap();
+    let message = transaction_parse_unsigned(&message_cbor, true).unwrap();
+
+    let signed_message = transaction_sign_with(&message, |digest| {
+        let digest: [u8; 32] = digest.try_into().unwrap();
+        Ok(sign_secp256k1_digest(&digest, &pk)?.bytes)
+    })
+    .unwrap();
+
+    assert_eq!(signed_message.message, message);
+    assert_eq!(
+        signed_message.signature,
+        transaction_sign_raw(&message, &pk).unwrap()
+    );
+    assert!(verify_signature(&signed_message.signature, &message_cbor).unwrap());
+
+    // A truncated signature from the remote signer is rejected
+    let truncated = transaction_sign_with(&message, |digest| {
+        let digest: [u8; 32] = digest.try_into().unwrap();
+        Ok(sign_secp256k1_digest(&digest, &pk)?.bytes[..64].to_vec())
+    });
+    assert!(truncated.is_err());
+}
+
+#[test]
+fn recover_pubkey_from_transaction_signature() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let pk = PrivateKey::try_from(test_value["private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let test_txs = common::load_test_vectors("../test_vectors/txs.json").unwrap();
+    let message_cbor = hex::decode(test_txs[0]["cbor"].as_str().unwrap()).unwrap();
+    let message = transaction_parse_unsigned(&message_cbor, true).unwrap();
+
+    let signature = transaction_sign_raw(&message, &pk).unwrap();
+
+    let public_key = recover_pubkey_from_signature(&signature, &message_cbor).unwrap();
+    let address = Address::new_secp256k1(&public_key.to_vec()).unwrap();
+
+    assert_eq!(address.payload(), message.from.payload());
+
+    assert!(
+        recover_pubkey_from_signature(&Signature::new_bls(vec![0; 96]), &message_cbor).is_err()
+    );
+}
+
+#[test]
+fn deserialize_params_generic_json() {
+    let propose_params = MessageParams::ProposeParams(multisig::ProposeParams {
+        to: Address::from_str("t01004").unwrap(),
+        value: TokenAmount::from(1000),
+        method: 2,
+        params: RawBytes::new(vec![0x81, 0x01]),
+    });
+    let params_b64 = base64::encode(propose_params.serialize().unwrap().bytes());
+
+    let json = deserialize_params_to_json(params_b64).unwrap();
+
+    assert_eq!(json, serde_json::json!(["00ec07", "0003e8", 2, "8101"]));
+
+    assert!(deserialize_params_to_json("not cbor".to_string()).is_err());
+}
+
+#[test]
+fn sign_transaction_checked_limits() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let pk = PrivateKey::try_from(test_value["private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let test_txs = common::load_test_vectors("../test_vectors/txs.json").unwrap();
+    let message_cbor = hex::decode(test_txs[0]["cbor"].as_str().unwrap()).unwrap();
+    let message = transaction_parse_unsigned(&message_cbor, true).unwrap();
+
+    let signed_message = transaction_sign_checked(&message, &pk, SignLimits::default()).unwrap();
+    assert_eq!(
+        signed_message.signature,
+        transaction_sign_raw(&message, &pk).unwrap()
+    );
+
+    let huge_gas = Message {
+        gas_limit: 10_000_000_001,
+        ..message.clone()
+    };
+    assert!(matches!(
+        transaction_sign_checked(&huge_gas, &pk, SignLimits::default()),
+        Err(SignerError::MessageLimitExceeded(_))
+    ));
+
+    let huge_params = Message {
+        params: RawBytes::new(vec![0; (64 << 10) + 1]),
+        ..message.clone()
+    };
+    assert!(matches!(
+        transaction_sign_checked(&huge_params, &pk, SignLimits::default()),
+        Err(SignerError::MessageLimitExceeded(_))
+    ));
+
+    // Params within the limit, but the whole message over 64 KiB
+    let huge_message = Message {
+        params: RawBytes::new(vec![0; (64 << 10) - 8]),
+        ..message.clone()
+    };
+    assert!(matches!(
+        transaction_sign_checked(&huge_message, &pk, SignLimits::default()),
+        Err(SignerError::MessageLimitExceeded(reason)) if reason.starts_with("message is")
+    ));
+
+    let limits = SignLimits {
+        max_params_bytes: 0,
+        max_gas_limit: message.gas_limit,
+    };
+    assert!(transaction_sign_checked(&message, &pk, limits).is_ok());
+}
+
+#[test]
+fn signed_message_lotus_mpool_json() {
+    let cbor = hex::decode(SIGNED_MESSAGE_CBOR).unwrap();
+
+    let signed_message = match transaction_parse(&cbor, false).unwrap() {
+        MessageTxAPI::SignedMessage(signed_message) => signed_message,
+        MessageTxAPI::Message(_) => panic!("Should be a signed message"),
+    };
+
+    // Lotus JSON shape of the message, CIDs computed independently from the CBOR (blake2b-256
+    // dag-cbor CIDv1 of the unsigned and signed message bytes)
+    const EXPECTED_JSON: &str = r#"{"Message":{"Version":0,"To":"f17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy","From":"f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba","Nonce":1,"Value":"100000","GasLimit":2500,"GasFeeCap":"1","GasPremium":"1","Method":0,"Params":"","CID":{"/":"bafy2bzaceb2tgt545wjpl7lgkznvhryyfv7fbj47audo7tosfw526lw4ilw62"}},"Signature":{"Type":1,"Data":"BjmEhQYMoqTeuXAn9Rj0VWk2DDhzpDA5JvppCacpnUxViDRjEgg2NY/zOWiC7g3CzxWWG9SVzfs94e4ui9N2jgE="},"CID":{"/":"bafy2bzaceb3m7fpwg5y5knmcej6z5atdtse6lt55usajxurcxvygwc4qe3aua"}}"#;
+
+    assert_eq!(
+        signed_message_to_json(&signed_message).unwrap(),
+        EXPECTED_JSON
+    );
+}
+
+#[test]
+fn sign_transaction_verified_key() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let pk = PrivateKey::try_from(test_value["private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let test_txs = common::load_test_vectors("../test_vectors/txs.json").unwrap();
+    let message_cbor = hex::decode(test_txs[0]["cbor"].as_str().unwrap()).unwrap();
+    let message = transaction_parse_unsigned(&message_cbor, true).unwrap();
+
+    let signed_message = transaction_sign_verified(&message, &pk).unwrap();
+    assert!(verify_signature(&signed_message.signature, &message_cbor).unwrap());
+
+    let other_key = key_from_test_seed(1, true).unwrap();
+    assert!(matches!(
+        transaction_sign_verified(&message, &other_key.private_key),
+        Err(SignerError::AddressMismatch)
+    ));
+}
+
+#[test]
+fn scan_addresses_from_mnemonic() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let mnemonic = test_value["mnemonic"].as_str().unwrap();
+    let language_code = test_value["language_code"].as_str().unwrap();
+
+    let childs: Vec<_> = test_value["childs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|child| child["address"].is_string())
+        .collect();
+
+    let paths: Vec<String> = childs
+        .iter()
+        .map(|child| child["path"].as_str().unwrap().to_string())
+        .collect();
+
+    let addresses = scan_mnemonic_addresses(mnemonic, "", language_code, &paths).unwrap();
+
+    assert_eq!(addresses.len(), childs.len());
+    for (address, child) in addresses.iter().zip(childs) {
+        assert_eq!(address, child["address"].as_str().unwrap());
+    }
+
+    assert!(
+        scan_mnemonic_addresses(mnemonic, "", language_code, &["not a path".to_string()]).is_err()
+    );
+}
+
+#[test]
+fn deserialize_multisig_propose_return() {
+    // Pending transaction 3, waiting for approvals
+    let pending = deserialize_propose_return("hAP0AEA=".to_string()).unwrap();
+    assert_eq!(pending.txn_id.0, 3);
+    assert!(!pending.applied);
+    assert!(pending.ret.bytes().is_empty());
+
+    // Transaction 5, applied right away with a return value
+    let applied = deserialize_propose_return("hAX1AEMKCww=".to_string()).unwrap();
+    assert_eq!(applied.txn_id.0, 5);
+    assert!(applied.applied);
+    assert_eq!(applied.ret.bytes(), &[0x0a, 0x0b, 0x0c]);
+
+    let json = serde_json::to_value(&api::MessageReturn::ProposeReturn(applied)).unwrap();
+    assert_eq!(json["TxnID"], 5);
+    assert_eq!(json["Applied"], true);
+    assert_eq!(json["Ret"], "CgsM");
+
+    assert!(deserialize_propose_return("hAP0".to_string()).is_err());
+}
+
+#[test]
+fn parse_transaction_strict_encoding() {
+    let test_txs = common::load_test_vectors("../test_vectors/txs.json").unwrap();
+    let cbor_hex = test_txs[0]["cbor"].as_str().unwrap();
+
+    assert!(transaction_parse_strict(&hex::decode(cbor_hex).unwrap(), true).is_ok());
+    assert!(transaction_parse_strict(&hex::decode(SIGNED_MESSAGE_CBOR).unwrap(), true).is_ok());
+
+    // Nonce 1 encoded on 2 bytes (0x18 0x01) instead of 1 (0x01)
+    let non_canonical = cbor_hex.replace("71160144", "7116180144");
+    let non_canonical = hex::decode(non_canonical).unwrap();
+
+    assert!(transaction_parse(&non_canonical, true).is_ok());
+    assert!(matches!(
+        transaction_parse_strict(&non_canonical, true),
+        Err(SignerError::NonCanonicalCbor)
+    ));
+}
+
+#[test]
+fn private_key_base64_variants() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let bls_private_key = test_value["bls_private_key"].as_str().unwrap();
+    let expected = PrivateKey::try_from(bls_private_key.to_string()).unwrap();
+
+    let variants = [
+        format!("  {}\n", bls_private_key),
+        bls_private_key.trim_end_matches('=').to_string(),
+        bls_private_key.replace('+', "-").replace('/', "_"),
+        bls_private_key
+            .replace('+', "-")
+            .replace('/', "_")
+            .trim_end_matches('=')
+            .to_string(),
+    ];
+
+    for variant in variants.iter() {
+        let private_key = PrivateKey::try_from(variant.to_string()).unwrap();
+        assert_eq!(private_key.0, expected.0);
+    }
+
+    assert!(matches!(
+        PrivateKey::try_from("not base64 at all!".to_string()),
+        Err(SignerError::InvalidBase64Key(_))
+    ));
+    // Valid base64 but not a 32 bytes key
+    assert!(matches!(
+        PrivateKey::try_from("AAAA".to_string()),
+        Err(SignerError::InvalidKeyLength { .. })
+    ));
+}
+
+#[test]
+fn sign_transaction_to_cbor() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let pk = PrivateKey::try_from(test_value["private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let test_txs = common::load_test_vectors("../test_vectors/txs.json").unwrap();
+    let message_cbor = hex::decode(test_txs[0]["cbor"].as_str().unwrap()).unwrap();
+    let message = transaction_parse_unsigned(&message_cbor, true).unwrap();
+
+    let signed_cbor = transaction_sign_to_cbor(&message, &pk).unwrap();
+    let signed_message = transaction_sign(&message, &pk).unwrap();
+
+    match transaction_parse(&signed_cbor, true).unwrap() {
+        MessageTxAPI::SignedMessage(parsed) => {
+            assert_eq!(parsed.message, signed_message.message);
+            assert_eq!(parsed.signature, signed_message.signature);
+        }
+        MessageTxAPI::Message(_) => panic!("Should be a signed message"),
+    }
+
+    assert_eq!(
+        transaction_sign_to_cbor_hex(&message, &pk).unwrap(),
+        hex::encode(&signed_cbor)
+    );
+}
+
+#[test]
+fn verify_aggregated_signature_known_keys() {
+    let fixture = common::bls_aggregate_fixture(7, 3);
+    let public_keys: Vec<_> = fixture
+        .private_keys
+        .iter()
+        .map(|private_key| private_key.public_key())
+        .collect();
+    let cbor_messages = fixture.cbor_messages;
+    let sig = fixture.signature;
+
+    assert_eq!(
+        verify_aggregated_signature_with_keys(&sig, &cbor_messages, &public_keys).unwrap(),
+        verify_aggregated_signature(&sig, &cbor_messages).unwrap()
+    );
+    assert!(verify_aggregated_signature_with_keys(&sig, &cbor_messages, &public_keys).unwrap());
+
+    // Keys not matching the messages
+    let mut swapped_keys = public_keys.clone();
+    swapped_keys.swap(0, 1);
+    assert!(!verify_aggregated_signature_with_keys(&sig, &cbor_messages, &swapped_keys).unwrap());
+
+    assert!(matches!(
+        verify_aggregated_signature_with_keys(&sig, &cbor_messages, &public_keys[..2]),
+        Err(SignerError::LengthMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_multisig_threshold_met() {
+    let signer_1 = Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap();
+    let signer_2 = Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap();
+    let signer_2_mainnet = Address::from_str("f17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap();
+    let stranger = Address::from_str("t137sjdbgunloi7couiy4l5nc7pd6k2jmq32vizpy").unwrap();
+
+    let signers = [signer_1, signer_2];
+
+    assert!(multisig_threshold_met(&signers, &[signer_1, signer_2], 2));
+    assert!(multisig_threshold_met(
+        &signers,
+        &[signer_1, signer_2_mainnet],
+        2
+    ));
+    assert!(!multisig_threshold_met(&signers, &[signer_1, signer_1], 2));
+    assert!(!multisig_threshold_met(&signers, &[signer_1, stranger], 2));
+    assert!(multisig_threshold_met(&signers, &[], 0));
+}
+
+#[test]
+fn test_ledger_display_fields() {
+    let message = Message {
+        version: 0,
+        to: Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+        from: Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap(),
+        sequence: 7,
+        value: TokenAmount::from_str("1500000000000000000").unwrap(),
+        gas_limit: 25000,
+        gas_fee_cap: TokenAmount::from_str("2500").unwrap(),
+        gas_premium: TokenAmount::from_str("2500").unwrap(),
+        method_num: 0,
+        params: RawBytes::new(vec![]),
+    };
+
+    let fields = ledger_display_fields(&message, Network::Mainnet).unwrap();
+
+    assert_eq!(
+        fields[0],
+        (
+            "To".to_string(),
+            "f17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy".to_string()
+        )
+    );
+    assert_eq!(fields[2], ("Nonce".to_string(), "7".to_string()));
+    assert_eq!(fields[3], ("Value".to_string(), "FIL 1.5".to_string()));
+    assert_eq!(
+        fields[5],
+        (
+            "Gas Fee Cap".to_string(),
+            "FIL 0.0000000000000025".to_string()
+        )
+    );
+    assert_eq!(fields.len(), 8);
+}
+
+#[test]
+fn test_multisig_propose() {
+    let multisig_address = Address::from_str("t01004").unwrap();
+    let from = Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap();
+    let to = Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap();
+
+    let gas = api::GasParams {
+        gas_limit: 25000,
+        gas_fee_cap: TokenAmount::from(2500),
+        gas_premium: TokenAmount::from(2500),
+    };
+
+    let message = api::multisig_propose(
+        multisig_address,
+        to,
+        TokenAmount::from(1000),
+        0,
+        RawBytes::default(),
+        from,
+        7,
+        gas,
+    )
+    .unwrap();
+
+    assert_eq!(message.to, multisig_address);
+    assert_eq!(message.from, from);
+    assert_eq!(message.sequence, 7);
+    assert_eq!(message.value, TokenAmount::from(0));
+    assert_eq!(message.gas_limit, 25000);
+    assert_eq!(message.method_num, multisig::Method::Propose as u64);
+
+    let params = deserialize_params(
+        base64::encode(message.params.bytes()),
+        ActorType::Multisig,
+        message.method_num,
+    )
+    .unwrap();
+
+    match params {
+        MessageParams::ProposeParams(p) => {
+            // CBOR addresses don't carry a network
+            assert_eq!(p.to.payload(), to.payload());
+            assert_eq!(p.value, TokenAmount::from(1000));
+            assert_eq!(p.method, 0);
+            assert!(p.params.bytes().is_empty());
+        }
+        _ => panic!("Should be ProposeParams"),
+    }
+}
+
+#[test]
+fn test_multisig_approve_and_cancel() {
+    let multisig_address = Address::from_str("t01004").unwrap();
+    let from = Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap();
+
+    let proposal = || ProposalHashDataAPI {
+        requester: Some(Address::from_str("t01001").unwrap()),
+        to: Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+        value: TokenAmount::from(1000),
+        method: 0,
+        params: RawBytes::default(),
+    };
+    let expected_hash = compute_proposal_hash(proposal()).unwrap();
+
+    let gas = api::GasParams {
+        gas_limit: 25000,
+        gas_fee_cap: TokenAmount::from(2500),
+        gas_premium: TokenAmount::from(2500),
+    };
+
+    let approve =
+        api::multisig_approve(multisig_address, 3, proposal(), from, 8, gas.clone()).unwrap();
+    let cancel = api::multisig_cancel(multisig_address, 3, proposal(), from, 9, gas).unwrap();
+
+    assert_eq!(approve.method_num, multisig::Method::Approve as u64);
+    assert_eq!(cancel.method_num, multisig::Method::Cancel as u64);
+    assert_eq!(approve.params, cancel.params);
+
+    for message in [approve, cancel] {
+        assert_eq!(message.to, multisig_address);
+
+        let params = deserialize_params(
+            base64::encode(message.params.bytes()),
+            ActorType::Multisig,
+            message.method_num,
+        )
+        .unwrap();
+
+        match params {
+            MessageParams::TxnIDParams(p) => {
+                assert_eq!(p.id.0, 3);
+                assert_eq!(base64::encode(p.proposal_hash), expected_hash);
+            }
+            _ => panic!("Should be TxnIDParams"),
+        }
+    }
+}
+
+#[test]
+fn test_create_multisig_message() {
+    let from = Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap();
+    let signers = vec![
+        from,
+        Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+    ];
+
+    let gas = api::GasParams {
+        gas_limit: 25000,
+        gas_fee_cap: TokenAmount::from(2500),
+        gas_premium: TokenAmount::from(2500),
+    };
+
+    let message = api::create_multisig_message(
+        signers.clone(),
+        2,
+        100,
+        10,
+        TokenAmount::from(1000),
+        from,
+        5,
+        gas.clone(),
+        7,
+    )
+    .unwrap();
+
+    assert_eq!(message.to, Address::from_str("t01").unwrap());
+    assert_eq!(message.value, TokenAmount::from(1000));
+    assert_eq!(message.sequence, 5);
+
+    let exec_params = match deserialize_params(
+        base64::encode(message.params.bytes()),
+        ActorType::Init,
+        message.method_num,
+    )
+    .unwrap()
+    {
+        MessageParams::ExecParams(p) => p,
+        _ => panic!("Should be ExecParams"),
+    };
+
+    assert_eq!(
+        exec_params.code_cid,
+        builtin_actor_code_cid(7, "multisig").unwrap()
+    );
+
+    let params = deserialize_constructor_params(
+        base64::encode(exec_params.constructor_params.bytes()),
+        exec_params.code_cid.to_string(),
+    )
+    .unwrap();
+
+    match params {
+        MessageParams::MultisigConstructorParams(p) => {
+            // CBOR addresses don't carry a network
+            let payloads: Vec<_> = p.signers.iter().map(|signer| signer.payload()).collect();
+            let expected: Vec<_> = signers.iter().map(|signer| signer.payload()).collect();
+            assert_eq!(payloads, expected);
+            assert_eq!(p.num_approvals_threshold, 2);
+            assert_eq!(p.unlock_duration, 100);
+            assert_eq!(p.start_epoch, 10);
+        }
+        _ => panic!("Should be MultisigConstructorParams"),
+    }
+
+    // No code CID known for bundles
+    assert!(matches!(
+        api::create_multisig_message(signers, 2, 0, 0, TokenAmount::from(0), from, 5, gas, 8),
+        Err(SignerError::UnsupportedCodeCid(_))
+    ));
+}
+
+#[test]
+fn test_create_paych_message() {
+    let from = Address::from_str("t137sjdbgunloi7couiy4l5nc7pd6k2jmq32vizpy").unwrap();
+    let to = Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap();
+
+    let gas = api::GasParams {
+        gas_limit: 25000,
+        gas_fee_cap: TokenAmount::from(2500),
+        gas_premium: TokenAmount::from(2500),
+    };
+
+    let message = api::create_paych_message(from, to, TokenAmount::from(10000), 3, gas, 7).unwrap();
+
+    assert_eq!(message.to, Address::from_str("t01").unwrap());
+    assert_eq!(message.from, from);
+    assert_eq!(message.value, TokenAmount::from(10000));
+
+    // The channel created by this message, to sign vouchers for
+    assert_eq!(
+        compute_paych_address(&message.from, message.sequence)
+            .unwrap()
+            .to_string(),
+        "t24acjqhdetck7irsvmn2p6jpuwnouzjxuoa22rva"
+    );
+
+    let exec_params = match deserialize_params(
+        base64::encode(message.params.bytes()),
+        ActorType::Init,
+        message.method_num,
+    )
+    .unwrap()
+    {
+        MessageParams::ExecParams(p) => p,
+        _ => panic!("Should be ExecParams"),
+    };
+
+    let params = deserialize_constructor_params(
+        base64::encode(exec_params.constructor_params.bytes()),
+        exec_params.code_cid.to_string(),
+    )
+    .unwrap();
+
+    match params {
+        MessageParams::PaychConstructorParams(p) => {
+            // CBOR addresses don't carry a network
+            assert_eq!(p.from.payload(), from.payload());
+            assert_eq!(p.to.payload(), to.payload());
+        }
+        _ => panic!("Should be PaychConstructorParams"),
+    }
+}
+
+#[test]
+fn test_build_voucher_redemption_message() {
+    let test_value = common::load_test_vectors("../test_vectors/voucher.json").unwrap();
+    let voucher_base64 = test_value["sign"]["signed_voucher_base64"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let channel = Address::from_str(
+        test_value["sign"]["voucher"]["payment_channel_address"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap();
+    let from = Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap();
+
+    let gas = api::GasParams {
+        gas_limit: 25000,
+        gas_fee_cap: TokenAmount::from(2500),
+        gas_premium: TokenAmount::from(2500),
+    };
+
+    let message = api::build_voucher_redemption_message(
+        channel,
+        from,
+        voucher_base64.clone(),
+        vec![],
+        4,
+        gas.clone(),
+    )
+    .unwrap();
+
+    assert_eq!(message.to, channel);
+    assert_eq!(message.sequence, 4);
+    assert_eq!(message.gas_limit, 25000);
+
+    let params = deserialize_params(
+        base64::encode(message.params.bytes()),
+        ActorType::PaymentChannel,
+        message.method_num,
+    )
+    .unwrap();
+
+    match params {
+        MessageParams::UpdateChannelStateParams(params) => {
+            assert_eq!(params.sv.lane, 1);
+            assert_eq!(params.sv.nonce, 1);
+            assert!(params.secret.is_empty());
+        }
+        _ => panic!("Should be UpdateChannelStateParams"),
+    }
+
+    // voucher of another channel
+    assert!(
+        api::build_voucher_redemption_message(from, from, voucher_base64, vec![], 4, gas).is_err()
+    );
+}
+
+#[test]
+fn sign_digest_go_ethereum_key() {
+    // Key and address from go-ethereum `crypto_test.go` (TestSign). go-ethereum doesn't publish
+    // the signature itself, the expected bytes below were produced by this crate; the vector part
+    // is the recovered address.
+    let pk = PrivateKey::try_from(
+        hex::decode("289c2857d4598e37fb9647507e47a309d6133539bf21a8b9cb6df88fd5232032").unwrap(),
+    )
+    .unwrap();
+
+    let signature = sign_secp256k1_digest(&utils::keccak256(b"foo"), &pk).unwrap();
+
+    assert_eq!(
+        hex::encode(&signature.bytes),
+        "d155e94305af7e07dd8c32873e5c03cb95c9e05960ef85be9c07f671da58c73718c19adc397a211aa9e87e519e2038c5a3b658618db335f74f800b8e0cfeef4401"
+    );
+
+    let public_key = libsecp256k1::recover(
+        &libsecp256k1::Message::parse(&utils::keccak256(b"foo")),
+        &libsecp256k1::Signature::parse_standard_slice(&signature.bytes[..64]).unwrap(),
+        &libsecp256k1::RecoveryId::parse(signature.bytes[64]).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        hex::encode(&utils::keccak256(&public_key.serialize()[1..])[12..]),
+        "970e8128ab834e8eac17ab8e3812f010678cf791"
+    );
+
+    // Legacy Ethereum recovery id is 27/28
+    let eth_signature = utils::to_eth_recoverable(&signature).unwrap();
+    assert_eq!(eth_signature[64], 28);
+}
+
+#[test]
+fn sign_transaction_eth() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let pk = PrivateKey::try_from(test_value["private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let message = Message {
+        version: 0,
+        to: Address::from_str("t01234").unwrap(),
+        from: Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap(),
+        sequence: 3,
+        value: TokenAmount::from_str("1000000000000000000").unwrap(),
+        gas_limit: 1000000,
+        gas_fee_cap: TokenAmount::from_str("200000").unwrap(),
+        gas_premium: TokenAmount::from_str("1000").unwrap(),
+        method_num: 0,
+        params: RawBytes::new(vec![]),
+    };
+
+    // keccak256 of the EIP-1559 transaction
+    // 02f08304cb2f038203e883030d40830f424094ff000000000000000000000000000000000004d2880de0b6b3a764000080c0
+    let signature = transaction_sign_eth_raw(&message, &pk).unwrap();
+
+    assert_eq!(
+        hex::encode(&signature.bytes),
+        "25f7a4382d70ab12e28c5d3b25db864d6acd517307ee7a7b07004dfdd4ec887d1c183beff1ee85482d2acdd63d5f66cea5113f44d731e52bbd6c02ffeab5b5d900"
+    );
+
+    // Same signature with the EIP-155 `v` of the calibration network (chain id 314159)
+    let (r, s, v) = transaction_sign_eth_rsv(&message, &pk, true).unwrap();
+    assert_eq!(r[..], signature.bytes[..32]);
+    assert_eq!(s[..], signature.bytes[32..64]);
+    assert_eq!(v, 628353);
+    assert_eq!(
+        utils::from_eip155_v(v, 314159).unwrap(),
+        signature.bytes[64]
+    );
+
+    let (_, _, v) = transaction_sign_eth_rsv(&message, &pk, false).unwrap();
+    assert_eq!(v, 0);
+
+    // Recipient without an Ethereum form
+    let message = Message {
+        to: Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+        ..message
+    };
+    assert!(transaction_sign_eth_raw(&message, &pk).is_err());
+}
+
+#[test]
+fn sign_transaction_delegated() {
+    let key = key_from_test_seed(1, true).unwrap();
+    let public_key = match &key.public_key {
+        PublicKey::SECP256K1PublicKey(public_key) => public_key.serialize(),
+        _ => panic!("Should be a secp256k1 key"),
+    };
+    let eth_address: [u8; 20] = utils::keccak256(&public_key[1..])[12..].try_into().unwrap();
+    let sender = utils::eth_to_f4(&eth_address, Network::Testnet).unwrap();
+    assert!(sender.starts_with("t410f"));
+
+    // The delegated sender can't be set, `from` only gives the network
+    let message = Message {
+        version: 0,
+        to: Address::from_str("t01234").unwrap(),
+        from: Address::from_str("t01001").unwrap(),
+        sequence: 3,
+        value: TokenAmount::from_str("1000000000000000000").unwrap(),
+        gas_limit: 1000000,
+        gas_fee_cap: TokenAmount::from_str("200000").unwrap(),
+        gas_premium: TokenAmount::from_str("1000").unwrap(),
+        method_num: 0,
+        params: RawBytes::new(vec![]),
+    };
+
+    let signature = transaction_sign_delegated_raw(&message, &sender, &key.private_key).unwrap();
+    assert_eq!(
+        signature,
+        transaction_sign_eth_raw(&message, &key.private_key).unwrap()
+    );
+
+    // The chain id comes from the sender network
+    let mainnet_sender = utils::eth_to_f4(&eth_address, Network::Mainnet).unwrap();
+    assert_ne!(
+        transaction_sign_delegated_raw(&message, &mainnet_sender, &key.private_key).unwrap(),
+        signature
+    );
+
+    // Key of another account
+    let other_key = key_from_test_seed(2, true).unwrap();
+    assert!(matches!(
+        transaction_sign_delegated_raw(&message, &sender, &other_key.private_key),
+        Err(SignerError::AddressMismatch)
+    ));
+
+    // Not a f410 address
+    assert!(transaction_sign_delegated_raw(&message, &key.address, &key.private_key).is_err());
+}
+
+#[test]
+fn verify_mainnet_signature() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let pk = PrivateKey::try_from(test_value["private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let message = Message {
+        version: 0,
+        to: Address::from_str("f17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+        from: Address::from_str("f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap(),
+        sequence: 1,
+        value: TokenAmount::from_str("100000").unwrap(),
+        gas_limit: 25000,
+        gas_fee_cap: TokenAmount::from_str("2500").unwrap(),
+        gas_premium: TokenAmount::from_str("2500").unwrap(),
+        method_num: 0,
+        params: RawBytes::new(vec![]),
+    };
+    let message_cbor = transaction_serialize(&message).unwrap();
+
+    let signature = transaction_sign_raw(&message, &pk).unwrap();
+    assert!(verify_signature(&signature, &message_cbor).unwrap());
+
+    // Signature of another mainnet account
+    let other_key = key_from_test_seed(1, false).unwrap();
+    let other_signature = transaction_sign_raw(&message, &other_key.private_key).unwrap();
+    assert!(!verify_signature(&other_signature, &message_cbor).unwrap());
+}
+
+#[test]
+fn sign_and_verify_bytes() {
+    let data = b"login nonce 42";
+
+    let wallet = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let pk = PrivateKey::try_from(wallet["private_key"].as_str().unwrap().to_string()).unwrap();
+    let secp_key = key_recover(&pk, false).unwrap();
+    let secp_address = Address::from_str(&secp_key.address).unwrap();
+
+    let bls_wallet = common::load_test_vectors("../test_vectors/bls_wallet.json").unwrap();
+    let bls_pk =
+        PrivateKey::try_from(bls_wallet["bls_private_key"].as_str().unwrap().to_string()).unwrap();
+    let bls_key = key_recover_bls(&bls_pk, false).unwrap();
+    let bls_address = Address::from_str(&bls_key.address).unwrap();
+
+    for (private_key, scheme, address) in [
+        (&pk, SignatureType::Secp256k1, &secp_address),
+        (&bls_pk, SignatureType::BLS, &bls_address),
+    ] {
+        let signature = sign_bytes(data, private_key, scheme).unwrap();
+        assert_eq!(signature.sig_type, scheme);
+
+        assert!(verify_bytes(data, &signature, address).unwrap());
+        assert!(!verify_bytes(b"login nonce 43", &signature, address).unwrap());
+
+        // Not interchangeable with a signature over the raw data
+        let raw_signature = sign_signing_bytes(data, private_key, scheme).unwrap();
+        assert!(!verify_bytes(data, &raw_signature, address).unwrap());
+    }
+
+    let secp_signature = sign_bytes(data, &pk, SignatureType::Secp256k1).unwrap();
+    assert!(!verify_bytes(data, &secp_signature, &bls_address).unwrap());
+}
+
+#[test]
+fn signed_bytes_are_not_transaction_signatures() {
+    let wallet = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let pk = PrivateKey::try_from(wallet["private_key"].as_str().unwrap().to_string()).unwrap();
+    let address = Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap();
+
+    let test_txs = common::load_test_vectors("../test_vectors/txs.json").unwrap();
+    let message_cbor = hex::decode(test_txs[0]["cbor"].as_str().unwrap()).unwrap();
+    let message = transaction_parse_unsigned(&message_cbor, true).unwrap();
+    let signing_bytes = message.to_signing_bytes();
+
+    // A blob equal to the transaction signing bytes doesn't give a transaction signature
+    let bytes_signature = sign_bytes(&signing_bytes, &pk, SignatureType::Secp256k1).unwrap();
+    assert!(verify_bytes(&signing_bytes, &bytes_signature, &address).unwrap());
+    assert!(!verify_signature(&bytes_signature, &message_cbor).unwrap());
+    assert!(!verify_signature_for_message(&bytes_signature, &message, Network::Testnet).unwrap());
+
+    // And a transaction signature isn't valid for the blob
+    let transaction_signature = transaction_sign_raw(&message, &pk).unwrap();
+    assert!(verify_signature(&transaction_signature, &message_cbor).unwrap());
+    assert!(!verify_bytes(&signing_bytes, &transaction_signature, &address).unwrap());
+}
+
+#[test]
+fn verify_high_s_signature() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let pk = PrivateKey::try_from(test_value["private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let test_txs = common::load_test_vectors("../test_vectors/txs.json").unwrap();
+    let message_cbor = hex::decode(test_txs[0]["cbor"].as_str().unwrap()).unwrap();
+    let message = transaction_parse_unsigned(&message_cbor, true).unwrap();
+
+    let signature = transaction_sign_raw(&message, &pk).unwrap();
+    assert!(verify_signature_strict(&signature, &message_cbor).unwrap());
+
+    // Same signature with S replaced by n - S, which flips the recovery id
+    let mut signature_rs =
+        libsecp256k1::Signature::parse_standard_slice(&signature.bytes[..64]).unwrap();
+    signature_rs.s = -signature_rs.s;
+    let mut high_s_bytes = signature_rs.serialize().to_vec();
+    high_s_bytes.push(signature.bytes[64] ^ 0x01);
+    let high_s_signature = Signature::new_secp256k1(high_s_bytes);
+
+    assert!(verify_signature(&high_s_signature, &message_cbor).unwrap());
+    assert!(matches!(
+        verify_signature_strict(&high_s_signature, &message_cbor),
+        Err(SignerError::MalleableSignature)
+    ));
+
+    // A truncated signature is rejected instead of panicking
+    let truncated_signature = Signature::new_secp256k1(signature.bytes[..32].to_vec());
+    assert!(matches!(
+        verify_signature_strict(&truncated_signature, &message_cbor),
+        Err(SignerError::InvalidSignatureLength(
+            SignatureType::Secp256k1,
+            65,
+            32
+        ))
+    ));
+}
+
+#[test]
+fn verify_signature_for_parsed_message() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let pk = PrivateKey::try_from(test_value["private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let bls_test_value = common::load_test_vectors("../test_vectors/bls_wallet.json").unwrap();
+    let bls_pk = PrivateKey::try_from(
+        bls_test_value["bls_private_key"]
+            .as_str()
+            .unwrap()
+            .to_string(),
+    )
+    .unwrap();
+    let bls_key = key_recover_bls(&bls_pk, false).unwrap();
+
+    for (from, private_key) in [
+        ("f1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba", &pk),
+        (bls_key.address.as_str(), &bls_pk),
+    ] {
+        let message = Message {
+            version: 0,
+            to: Address::from_str("f17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+            from: Address::from_str(from).unwrap(),
+            sequence: 1,
+            value: TokenAmount::from_str("100000").unwrap(),
+            gas_limit: 25000,
+            gas_fee_cap: TokenAmount::from_str("2500").unwrap(),
+            gas_premium: TokenAmount::from_str("2500").unwrap(),
+            method_num: 0,
+            params: RawBytes::new(vec![]),
+        };
+        let message_cbor = transaction_serialize(&message).unwrap();
+
+        let signature = transaction_sign_raw(&message, private_key).unwrap();
+        let mut tampered_signature = signature.clone();
+        tampered_signature.bytes[10] ^= 0x01;
+
+        for signature in [&signature, &tampered_signature] {
+            let from_cbor = verify_signature(signature, &message_cbor).unwrap_or(false);
+            let from_message = verify_signature_for_message(signature, &message, Network::Mainnet)
+                .unwrap_or(false);
+
+            assert_eq!(from_cbor, from_message);
+        }
+
+        assert!(verify_signature_for_message(&signature, &message, Network::Mainnet).unwrap());
+        assert!(!verify_signature_for_message(&signature, &message, Network::Testnet).unwrap());
+    }
+}
+
+#[cfg(feature = "with-hex-api")]
+#[test]
+fn sign_transaction_hex() {
+    let test_value = common::load_test_vectors("../test_vectors/txs.json").unwrap();
+    let wallet = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let private_key = base64::decode(wallet["private_key"].as_str().unwrap()).unwrap();
+
+    let cbor_hex = test_value[0]["cbor"].as_str().unwrap();
+
+    let signed_hex = transaction_sign_hex(cbor_hex, &hex::encode(&private_key), true).unwrap();
+
+    let signed_cbor = hex::decode(&signed_hex).unwrap();
+    let signed_message = match transaction_parse(&signed_cbor, true).unwrap() {
+        MessageTxAPI::SignedMessage(signed_message) => signed_message,
+        MessageTxAPI::Message(_) => panic!("Should be a Signed Message!"),
+    };
+
+    assert_eq!(
+        hex::encode(signed_message.message.marshal_cbor().unwrap()),
+        cbor_hex
+    );
+    assert!(verify_signature(
+        &signed_message.signature,
+        &signed_message.message.marshal_cbor().unwrap()
+    )
+    .unwrap());
+
+    // Signed transactions can't be signed again
+    assert!(matches!(
+        transaction_sign_hex(&signed_hex, &hex::encode(&private_key), true),
+        Err(SignerError::UnexpectedSignedMessage)
+    ));
+}
+
+#[test]
+fn sign_transaction_batch() {
+    let test_value = common::load_test_vectors("../test_vectors/wallet.json").unwrap();
+    let pk = PrivateKey::try_from(test_value["private_key"].as_str().unwrap().to_string()).unwrap();
+
+    let messages: Vec<Message> = (0..1000)
+        .map(|nonce| Message {
+            version: 0,
+            to: Address::from_str("t17uoq6tp427uzv7fztkbsnn64iwotfrristwpryy").unwrap(),
+            from: Address::from_str("t1d2xrzcslx7xlbbylc5c3d5lvandqw4iwl6epxba").unwrap(),
+            sequence: nonce,
+            value: TokenAmount::from_str("100000").unwrap(),
+            gas_limit: 25000,
+            gas_fee_cap: TokenAmount::from_str("2500").unwrap(),
+            gas_premium: TokenAmount::from_str("2500").unwrap(),
+            method_num: 0,
+            params: RawBytes::new(vec![]),
+        })
+        .collect();
+
+    let signed_messages = transaction_sign_batch(&messages, &pk).unwrap();
+
+    assert_eq!(signed_messages.len(), messages.len());
+    signed_messages
+        .par_iter()
+        .zip(messages.par_iter())
+        .for_each(|(signed_message, message)| {
+            assert_eq!(&signed_message.message, message);
+
+            let message_cbor = transaction_serialize(message).unwrap();
+            assert!(verify_signature(&signed_message.signature, &message_cbor).unwrap());
+        });
+
+    // An invalid sender fails the whole batch
+    let mut invalid_messages = messages[..2].to_vec();
+    invalid_messages[1].from = Address::from_str("t01001").unwrap();
+    assert!(transaction_sign_batch(&invalid_messages, &pk).is_err());
+}